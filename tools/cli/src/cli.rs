@@ -1,6 +1,8 @@
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 use vacs_data_diagnostics::LogFormat;
+use vacs_data_validator::graph::GraphFormat;
+use vacs_data_validator::report::ReportFormat;
 
 #[derive(Debug, Parser)]
 #[command(name = "vacs-data", version, about = "vacs dataset tools")]
@@ -10,10 +12,10 @@ pub struct Cli {
     pub verbose: u8,
 
     /// Quiet mode (errors only)
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with = "verbose")]
     pub quiet: bool,
 
-    /// Logging output format. Supported: human, github
+    /// Logging output format. Supported: human, github, sarif, json, junit
     #[arg(long, default_value_t = LogFormat::Human)]
     pub log_format: LogFormat,
 
@@ -37,6 +39,98 @@ pub enum Command {
         /// Dataset root to validate
         #[arg(short, long)]
         input: Option<PathBuf>,
+
+        /// Comma-separated group of position IDs that are intentionally ambiguous, can be
+        /// specified multiple times (e.g. LOWI_E_APP,LOWI_S_APP)
+        #[arg(long, value_name = "POSITIONS")]
+        allow_ambiguous: Vec<String>,
+
+        /// Lint configuration file. Defaults to vacs-data.toml in the dataset root or its
+        /// parent directory
+        #[arg(long, value_name = "FILE")]
+        config: Option<PathBuf>,
+
+        /// Treat warnings as errors, exiting with a non-zero status if any are found
+        #[arg(long)]
+        deny_warnings: bool,
+
+        /// Append a Markdown summary of the run to this file. Defaults to $GITHUB_STEP_SUMMARY
+        /// with --log-format github
+        #[arg(long, value_name = "FILE")]
+        summary: Option<PathBuf>,
+    },
+
+    /// Simulate which position covers every station for a set of online logins
+    #[command(arg_required_else_help = true)]
+    Coverage {
+        /// Dataset root to load (positional).
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to load
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Online login in CALLSIGN:FREQUENCY[:FACILITY] format, can be specified multiple times.
+        /// The facility type is derived from the callsign suffix if omitted.
+        #[arg(short, long = "login", value_name = "LOGIN")]
+        logins: Vec<vacs_data_validator::coverage::Login>,
+
+        /// File containing one login per line in CALLSIGN:FREQUENCY[:FACILITY] format
+        #[arg(long, value_name = "FILE")]
+        logins_file: Option<PathBuf>,
+
+        /// Saved VATSIM data feed snapshot (vatsim-data.json) to read controller logins from
+        #[arg(long, value_name = "FILE")]
+        snapshot: Option<PathBuf>,
+
+        /// Only print coverage for stations of the given FIR directory
+        #[arg(long)]
+        fir: Option<String>,
+    },
+
+    /// Explain a diagnostic code in detail, or list all codes if none is given
+    Explain {
+        /// Code (e.g. VD0003) or rule name (e.g. ambiguous-positions) to explain
+        code: Option<String>,
+    },
+
+    /// Explain how the effective coverage list of a station is resolved from its parents
+    #[command(arg_required_else_help = true)]
+    ExplainStation {
+        /// Station ID to explain (e.g. LOWW_W_GND)
+        station: String,
+
+        /// Dataset root to load (positional).
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to load
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+
+    /// Explain how a controller login is matched to a position
+    #[command(arg_required_else_help = true)]
+    Match {
+        /// Callsign of the login (e.g. LOWW_E_TWR)
+        callsign: String,
+
+        /// Dataset root to load (positional).
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to load
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Frequency of the login (e.g. 121.300)
+        #[arg(long)]
+        freq: String,
+
+        /// Facility type of the login. Derived from the callsign suffix if omitted
+        #[arg(long)]
+        facility: Option<String>,
     },
 
     /// Import data from external sources, converting them to vacs dataset format
@@ -44,6 +138,187 @@ pub enum Command {
         #[command(subcommand)]
         cmd: ImportCommand,
     },
+
+    /// Export the station inheritance graph of a FIR or the whole dataset, including covering
+    /// positions and referencing profiles
+    #[command(arg_required_else_help = true)]
+    Graph {
+        /// Dataset root to load (positional).
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to load
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// FIR directory to limit the graph to. Defaults to the whole dataset
+        #[arg(long)]
+        fir: Option<String>,
+
+        /// Format of the graph. Supported: dot, mermaid
+        #[arg(long, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+
+        /// File to write the graph to. Defaults to stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Render a profile as a self-contained HTML preview
+    #[command(arg_required_else_help = true)]
+    RenderProfile {
+        /// Profile ID or path to a profile file
+        profile: String,
+
+        /// Dataset root to load (positional).
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to load
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// File to write the preview to. Defaults to stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Render every page of profiles as SVG, e.g. for diffing snapshots in CI
+    #[command(arg_required_else_help = true)]
+    RenderSnapshots {
+        /// Dataset root to load (positional).
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to load
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Profile ID or path to a profile file. Defaults to all profiles of the dataset
+        #[arg(short, long)]
+        profile: Option<String>,
+
+        /// Directory to write the snapshots to, one subdirectory per profile
+        #[arg(short, long, value_name = "DIR")]
+        output: PathBuf,
+    },
+
+    /// Generate reports on the coverage of a FIR
+    Report {
+        #[command(subcommand)]
+        cmd: ReportCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ReportCommand {
+    /// List the stations every position covers when it is the only controller online and when
+    /// all positions are online
+    #[command(arg_required_else_help = true)]
+    Footprint {
+        /// Dataset root to load (positional).
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to load
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// FIR directory to generate the report for
+        #[arg(long)]
+        fir: String,
+
+        /// Format of the report. Supported: markdown, csv
+        #[arg(long, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+
+        /// File to write the report to. Defaults to stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Export a station × position matrix holding the rank of each position in the station's
+    /// resolved coverage list
+    #[command(arg_required_else_help = true)]
+    Matrix {
+        /// Dataset root to load (positional).
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to load
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// FIR directory to generate the report for
+        #[arg(long)]
+        fir: String,
+
+        /// Format of the report. Supported: markdown, csv
+        #[arg(long, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+
+        /// File to write the report to. Defaults to stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Compute the smallest sets of positions covering every station of a FIR and the stations
+    /// only a single position can cover
+    #[command(arg_required_else_help = true)]
+    Staffing {
+        /// Dataset root to load (positional).
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to load
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// FIR directory to generate the report for
+        #[arg(long)]
+        fir: String,
+
+        /// Format of the report. Supported: markdown, csv
+        #[arg(long, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+
+        /// File to write the report to. Defaults to stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Analyze archived VATSIM data feed snapshots for logins that did not match any position
+    #[command(arg_required_else_help = true)]
+    Snapshots {
+        /// Directory containing data feed snapshots (positional)
+        #[arg(value_name = "INPUT", required_unless_present = "input")]
+        input_pos: Option<PathBuf>,
+
+        /// Dataset root to match logins against (positional)
+        #[arg(value_name = "DATASET", required_unless_present = "dataset")]
+        dataset_pos: Option<PathBuf>,
+
+        /// Directory containing data feed snapshots
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Dataset root to match logins against
+        #[arg(short, long)]
+        dataset: Option<PathBuf>,
+
+        /// FIR directory to analyze logins for
+        #[arg(long)]
+        fir: String,
+
+        /// Comma-separated callsign prefixes of logins to analyze, can be specified multiple
+        /// times. Defaults to the FIR's country code (e.g. EF)
+        #[arg(long, value_name = "PREFIXES")]
+        prefix: Vec<String>,
+
+        /// Format to use for proposed positions. Supported: toml, json
+        #[arg(short, long, default_value_t = vacs_data_importer::OutputFormat::Toml)]
+        format: vacs_data_importer::OutputFormat,
+    },
 }
 
 #[derive(Debug, Subcommand)]
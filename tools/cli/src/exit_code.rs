@@ -0,0 +1,75 @@
+//! Process exit codes, so scripts can tell failures apart without parsing the output. A
+//! successful run exits with `0`, invalid command line arguments exit with `2` (reported by
+//! clap), so no code below may reuse it.
+
+/// The dataset contains errors.
+pub const INVALID_DATASET: i32 = 1;
+/// An input path or argument is invalid, e.g. it does not exist.
+pub const INVALID_INPUT: i32 = 3;
+/// An input file could not be read or parsed.
+pub const UNREADABLE_INPUT: i32 = 4;
+/// An output file already exists and neither `--merge` nor `--overwrite` was given.
+pub const OUTPUT_EXISTS: i32 = 5;
+/// An output file could not be written.
+pub const WRITE_FAILED: i32 = 6;
+/// The dataset contains warnings and `--deny-warnings` was given.
+pub const DENIED_WARNINGS: i32 = 7;
+
+/// Maps an error to the exit code reported for it.
+pub trait ExitCode {
+    fn exit_code(&self) -> i32;
+
+    /// Stable code explained by `vacs-data explain`.
+    fn code(&self) -> Option<&'static str>;
+}
+
+impl ExitCode for vacs_data_validator::Error {
+    fn exit_code(&self) -> i32 {
+        use vacs_data_validator::Error;
+
+        match self {
+            Error::Dataset(_) => INVALID_DATASET,
+            Error::InputNotFound(_)
+            | Error::InputNotDirectory(_)
+            | Error::UnknownFir { .. }
+            | Error::UnknownStation { .. }
+            | Error::UnknownProfile { .. }
+            | Error::InvalidLogin { .. }
+            | Error::InvalidLoginArgument(_) => INVALID_INPUT,
+            Error::Read { .. } | Error::ParseToml { .. } | Error::ParseJson { .. } => {
+                UNREADABLE_INPUT
+            }
+            Error::Write { .. } => WRITE_FAILED,
+        }
+    }
+
+    fn code(&self) -> Option<&'static str> {
+        self.code()
+    }
+}
+
+impl ExitCode for vacs_data_importer::Error {
+    fn exit_code(&self) -> i32 {
+        use vacs_data_importer::Error;
+
+        match self {
+            Error::InputNotFound(_) | Error::InputNotDirectory(_) | Error::UnknownFir(_) => {
+                INVALID_INPUT
+            }
+            Error::Read { .. }
+            | Error::ParseToml { .. }
+            | Error::ParseJson { .. }
+            | Error::Convert { .. } => UNREADABLE_INPUT,
+            Error::OutputExists { .. } => OUTPUT_EXISTS,
+            Error::OutputNotDirectory(_)
+            | Error::CreateOutputDirectory { .. }
+            | Error::Serialize { .. }
+            | Error::Write { .. } => WRITE_FAILED,
+            Error::Validator(err) => err.exit_code(),
+        }
+    }
+
+    fn code(&self) -> Option<&'static str> {
+        self.code()
+    }
+}
@@ -1,18 +1,146 @@
 mod cli;
+mod exit_code;
 
-use crate::cli::{Cli, Command, ImportCommand};
+use crate::cli::{Cli, Command, ImportCommand, ReportCommand};
+use crate::exit_code::ExitCode;
 use clap::Parser;
+use std::path::PathBuf;
+use vacs_data_diagnostics::{Diagnostic, LogFormat, Severity, Verbosity};
+use vacs_data_validator::codes;
+use vacs_data_validator::config::{AllowEntry, Config};
+
+/// Flushes buffered diagnostics before exiting with the given code.
+fn exit(code: i32) -> ! {
+    vacs_data_diagnostics::flush();
+    std::process::exit(code);
+}
+
+/// Marks the run as aborted before exiting with the given code.
+fn abort(code: i32) -> ! {
+    vacs_data_diagnostics::abort();
+    exit(code);
+}
+
+/// Logs an error and exits with its exit code. Dataset errors have already been reported as
+/// diagnostics and are only summarized.
+fn fail<E: ExitCode + std::fmt::Display>(err: &E) -> ! {
+    let mut diagnostic = Diagnostic::new(Severity::Error, err.to_string());
+    diagnostic.code = err.code().map(ToString::to_string);
+    vacs_data_diagnostics::log::emit(&diagnostic);
+    abort(err.exit_code());
+}
 
 pub fn main() {
     let cli = Cli::parse();
-    vacs_data_diagnostics::init(cli.log_format);
+    vacs_data_diagnostics::init(
+        cli.log_format,
+        Verbosity::from_flags(cli.verbose, cli.quiet),
+    );
 
     match cli.cmd {
-        Command::Validate { input_pos, input } => {
+        Command::Validate {
+            input_pos,
+            input,
+            allow_ambiguous,
+            config,
+            deny_warnings,
+            summary,
+        } => {
             let input = input.or(input_pos).unwrap();
+            let summary = summary.or_else(|| {
+                (cli.log_format == LogFormat::GitHub)
+                    .then(|| std::env::var_os(vacs_data_validator::summary::GITHUB_STEP_SUMMARY))
+                    .flatten()
+                    .map(PathBuf::from)
+            });
+
+            let config = match config {
+                Some(path) => Config::load(&path),
+                None => Config::discover(&input),
+            };
+            let mut config = config.unwrap_or_else(|err| fail(&err));
+            for group in allow_ambiguous {
+                config.allow(
+                    "ambiguous-positions",
+                    AllowEntry::Group(group.split(',').map(|id| id.trim().to_string()).collect()),
+                );
+            }
 
-            if vacs_data_validator::validate(&input).is_err() {
-                std::process::exit(1);
+            match vacs_data_validator::validate(&input, &config, summary.as_deref()) {
+                Ok(Some(Severity::Error)) => exit(exit_code::INVALID_DATASET),
+                Ok(Some(Severity::Warning)) if deny_warnings => exit(exit_code::DENIED_WARNINGS),
+                Ok(_) => {}
+                Err(err) => fail(&err),
+            }
+        }
+        Command::Coverage {
+            input_pos,
+            input,
+            mut logins,
+            logins_file,
+            snapshot,
+            fir,
+        } => {
+            let input = input.or(input_pos).unwrap();
+
+            if let Some(logins_file) = logins_file {
+                match vacs_data_validator::coverage::read_logins(&logins_file) {
+                    Ok(l) => logins.extend(l),
+                    Err(err) => fail(&err),
+                }
+            }
+            if let Some(snapshot) = snapshot {
+                match vacs_data_validator::datafeed::read_snapshot(&snapshot) {
+                    Ok(l) => logins.extend(l),
+                    Err(err) => fail(&err),
+                }
+            }
+
+            if let Err(err) =
+                vacs_data_validator::coverage::simulate(&input, &logins, fir.as_deref())
+            {
+                fail(&err);
+            }
+        }
+        Command::Explain { code: None } => println!("{}", codes::list()),
+        Command::Explain { code: Some(code) } => match codes::lookup(&code) {
+            Some(code) => println!("{}", codes::explain(code)),
+            None => {
+                vacs_data_diagnostics::log::error(format_args!(
+                    "unknown code `{code}`, run `vacs-data explain` for a list of all codes"
+                ));
+                abort(exit_code::INVALID_INPUT);
+            }
+        },
+        Command::ExplainStation {
+            station,
+            input_pos,
+            input,
+        } => {
+            let input = input.or(input_pos).unwrap();
+
+            if let Err(err) = vacs_data_validator::explain::station(&input, &station) {
+                fail(&err);
+            }
+        }
+        Command::Match {
+            callsign,
+            input_pos,
+            input,
+            freq,
+            facility,
+        } => {
+            let input = input.or(input_pos).unwrap();
+            let login = match facility {
+                Some(facility) => format!("{callsign}:{freq}:{facility}"),
+                None => format!("{callsign}:{freq}"),
+            };
+            let result = login
+                .parse()
+                .map_err(vacs_data_validator::Error::InvalidLoginArgument)
+                .and_then(|login| vacs_data_validator::explain::login(&input, &login, &callsign));
+            if let Err(err) = result {
+                fail(&err);
             }
         }
         Command::Import {
@@ -30,10 +158,10 @@ pub fn main() {
             let input = input.or(input_pos).unwrap();
             let output = output.or(output_pos).unwrap();
 
-            if vacs_data_importer::vatglasses::parse(&input, &output, overwrite, merge, format)
-                .is_err()
+            if let Err(err) =
+                vacs_data_importer::vatglasses::parse(&input, &output, overwrite, merge, format)
             {
-                std::process::exit(1);
+                fail(&err);
             }
         }
         Command::Import {
@@ -53,13 +181,147 @@ pub fn main() {
             let output = output.or(output_pos).unwrap();
             let prefixes = prefixes.unwrap_or_default();
 
-            if vacs_data_importer::euroscope::parse(
+            if let Err(err) = vacs_data_importer::euroscope::parse(
                 &input, &output, &prefixes, overwrite, merge, format,
-            )
-            .is_err()
+            ) {
+                fail(&err);
+            }
+        }
+        Command::Graph {
+            input_pos,
+            input,
+            fir,
+            format,
+            output,
+        } => {
+            let input = input.or(input_pos).unwrap();
+
+            if let Err(err) = vacs_data_validator::graph::export(
+                &input,
+                fir.as_deref(),
+                format,
+                output.as_deref(),
+            ) {
+                fail(&err);
+            }
+        }
+        Command::RenderProfile {
+            profile,
+            input_pos,
+            input,
+            output,
+        } => {
+            let input = input.or(input_pos).unwrap();
+
+            if let Err(err) =
+                vacs_data_validator::render::html::export(&input, &profile, output.as_deref())
+            {
+                fail(&err);
+            }
+        }
+        Command::RenderSnapshots {
+            input_pos,
+            input,
+            profile,
+            output,
+        } => {
+            let input = input.or(input_pos).unwrap();
+
+            if let Err(err) =
+                vacs_data_validator::render::svg::export(&input, profile.as_deref(), &output)
+            {
+                fail(&err);
+            }
+        }
+        Command::Report {
+            cmd:
+                ReportCommand::Footprint {
+                    input_pos,
+                    input,
+                    fir,
+                    format,
+                    output,
+                },
+        } => {
+            let input = input.or(input_pos).unwrap();
+
+            if let Err(err) = vacs_data_validator::report::footprint::export(
+                &input,
+                &fir,
+                format,
+                output.as_deref(),
+            ) {
+                fail(&err);
+            }
+        }
+        Command::Report {
+            cmd:
+                ReportCommand::Matrix {
+                    input_pos,
+                    input,
+                    fir,
+                    format,
+                    output,
+                },
+        } => {
+            let input = input.or(input_pos).unwrap();
+
+            if let Err(err) =
+                vacs_data_validator::report::matrix::export(&input, &fir, format, output.as_deref())
             {
-                std::process::exit(1);
+                fail(&err);
+            }
+        }
+        Command::Report {
+            cmd:
+                ReportCommand::Staffing {
+                    input_pos,
+                    input,
+                    fir,
+                    format,
+                    output,
+                },
+        } => {
+            let input = input.or(input_pos).unwrap();
+
+            if let Err(err) = vacs_data_validator::report::staffing::export(
+                &input,
+                &fir,
+                format,
+                output.as_deref(),
+            ) {
+                fail(&err);
+            }
+        }
+        Command::Report {
+            cmd:
+                ReportCommand::Snapshots {
+                    input_pos,
+                    dataset_pos,
+                    input,
+                    dataset,
+                    fir,
+                    prefix,
+                    format,
+                },
+        } => {
+            let input = input.or(input_pos).unwrap();
+            let dataset = dataset.or(dataset_pos).unwrap();
+            let prefixes = prefix
+                .iter()
+                .flat_map(|p| p.split(','))
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+
+            if let Err(err) =
+                vacs_data_importer::snapshots::analyze(&input, &dataset, &fir, &prefixes, format)
+            {
+                fail(&err);
             }
         }
     }
+
+    vacs_data_diagnostics::flush();
 }
@@ -0,0 +1,116 @@
+use crate::Severity;
+use std::path::PathBuf;
+
+/// Position of a diagnostic in a dataset file. Lines and columns are 1-based, the end is
+/// inclusive of the last character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Location {
+    /// Location of a file as a whole, without a span, used if no more precise location is known.
+    #[must_use]
+    pub fn file(file: PathBuf) -> Self {
+        Self {
+            file,
+            line: 0,
+            column: 0,
+            end_line: 0,
+            end_column: 0,
+        }
+    }
+
+    /// Whether the location points at a span within the file rather than the whole file.
+    #[must_use]
+    pub fn has_span(&self) -> bool {
+        self.line > 0
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.has_span() {
+            write!(f, "{}:{}:{}", self.file.display(), self.line, self.column)
+        } else {
+            write!(f, "{}", self.file.display())
+        }
+    }
+}
+
+/// A single finding reported by the tools, independent of the output format.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable code explained by `vacs-data explain`, e.g. `VD0003`.
+    pub code: Option<String>,
+    /// ID of the lint rule that produced the diagnostic, if any.
+    pub rule: Option<String>,
+    /// FIR directory the diagnostic belongs to, if known.
+    pub fir: Option<String>,
+    pub context: Vec<String>,
+    pub location: Option<Location>,
+    /// Short note shown next to the underlined snippet in human output.
+    pub label: Option<String>,
+    /// Hint on how to fix the problem, e.g. similarly named IDs.
+    pub help: Option<String>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: None,
+            rule: None,
+            fir: None,
+            context: Vec::new(),
+            location: None,
+            label: None,
+            help: None,
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_rule(mut self, rule: impl Into<String>) -> Self {
+        self.rule = Some(rule.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_context(mut self, context: Vec<String>) -> Self {
+        self.fir = self.fir.or_else(|| context.first().cloned());
+        self.context = context;
+        self
+    }
+
+    #[must_use]
+    pub fn with_location(mut self, location: Option<Location>) -> Self {
+        self.location = location;
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{Diagnostic, Severity};
+use serde_json::{Value, json};
+
+/// Converts a diagnostic to the object printed per line in `json` mode.
+pub(crate) fn diagnostic(diagnostic: &Diagnostic) -> Value {
+    let mut value = json!({
+        "level": diagnostic.severity.as_str(),
+        "code": diagnostic.code,
+        "rule": diagnostic.rule,
+        "fir": diagnostic.fir,
+        "context": diagnostic.context,
+        "message": diagnostic.message,
+    });
+    if let Some(location) = &diagnostic.location {
+        value["file"] = json!(location.file.to_string_lossy().replace('\\', "/"));
+        if location.has_span() {
+            value["span"] = json!({
+                "start": { "line": location.line, "column": location.column },
+                "end": { "line": location.end_line, "column": location.end_column },
+            });
+        }
+    }
+    if let Some(label) = &diagnostic.label {
+        value["label"] = json!(label);
+    }
+    if let Some(help) = &diagnostic.help {
+        value["help"] = json!(help);
+    }
+    value
+}
+
+/// Converts a plain log message to an object without rule or location.
+pub(crate) fn message(severity: Severity, message: &str) -> Value {
+    json!({
+        "level": severity.as_str(),
+        "code": null,
+        "rule": null,
+        "fir": null,
+        "context": [],
+        "message": message,
+    })
+}
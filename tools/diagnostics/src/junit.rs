@@ -0,0 +1,145 @@
+use crate::{Diagnostic, Severity};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Check name used for diagnostics not produced by a lint rule, e.g. missing references.
+const GENERAL_CHECK: &str = "general";
+
+/// Suite name used for diagnostics not belonging to a FIR and plain log messages.
+const GLOBAL_SUITE: &str = "vacs-data";
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn line(diagnostic: &Diagnostic) -> String {
+    let mut line = match &diagnostic.code {
+        Some(code) => format!("{}[{code}]: ", diagnostic.severity),
+        None => format!("{}: ", diagnostic.severity),
+    };
+    if let Some(location) = &diagnostic.location {
+        let _ = write!(line, "{location}: ");
+    }
+    if !diagnostic.context.is_empty() {
+        let _ = write!(line, "[{}] ", diagnostic.context.join(" > "));
+    }
+    line.push_str(&diagnostic.message);
+    if let Some(help) = &diagnostic.help {
+        let _ = write!(line, " (help: {help})");
+    }
+    line
+}
+
+/// A test case failing if any of its diagnostics is an error. Warnings and infos are listed as
+/// output of a passing test case.
+fn testcase(out: &mut String, suite: &str, check: &str, lines: &[(Severity, String)]) {
+    let _ = write!(
+        out,
+        "    <testcase name=\"{}\" classname=\"{}\"",
+        escape(check),
+        escape(suite)
+    );
+    if lines.is_empty() {
+        out.push_str("/>\n");
+        return;
+    }
+    out.push_str(">\n");
+
+    let errors = lines
+        .iter()
+        .filter(|(severity, _)| *severity == Severity::Error)
+        .count();
+    let text = lines
+        .iter()
+        .map(|(_, line)| escape(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if errors > 0 {
+        let _ = writeln!(
+            out,
+            "      <failure message=\"{errors} error(s)\" type=\"error\">{text}</failure>"
+        );
+    } else {
+        let _ = writeln!(out, "      <system-out>{text}</system-out>");
+    }
+    out.push_str("    </testcase>\n");
+}
+
+/// Builds a JUnit XML report with one test suite per FIR and one test case per check.
+///
+/// FIRs and checks without any diagnostics are only included if they have been declared,
+/// otherwise there would be no passing test cases.
+pub(crate) fn render(
+    diagnostics: &[Diagnostic],
+    notifications: &[(Severity, String)],
+    firs: &[String],
+    checks: &[String],
+) -> String {
+    type Suites = BTreeMap<String, BTreeMap<String, Vec<(Severity, String)>>>;
+
+    let mut suites = Suites::new();
+    for fir in firs {
+        let suite = suites.entry(fir.clone()).or_default();
+        for check in checks.iter().map(String::as_str).chain([GENERAL_CHECK]) {
+            suite.entry(check.to_string()).or_default();
+        }
+    }
+    for d in diagnostics {
+        suites
+            .entry(d.fir.clone().unwrap_or_else(|| GLOBAL_SUITE.to_string()))
+            .or_default()
+            .entry(d.rule.clone().unwrap_or_else(|| GENERAL_CHECK.to_string()))
+            .or_default()
+            .push((d.severity, line(d)));
+    }
+    if !notifications.is_empty() {
+        suites
+            .entry(GLOBAL_SUITE.to_string())
+            .or_default()
+            .entry(GENERAL_CHECK.to_string())
+            .or_default()
+            .extend(
+                notifications
+                    .iter()
+                    .map(|(severity, message)| (*severity, format!("{severity}: {message}"))),
+            );
+    }
+
+    let is_failure = |lines: &Vec<(Severity, String)>| {
+        lines
+            .iter()
+            .any(|(severity, _)| *severity == Severity::Error)
+    };
+    let tests = suites.values().map(BTreeMap::len).sum::<usize>();
+    let failures = suites
+        .values()
+        .flat_map(BTreeMap::values)
+        .filter(|lines| is_failure(lines))
+        .count();
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"vacs-data\" tests=\"{tests}\" failures=\"{failures}\">"
+    );
+    for (suite, cases) in &suites {
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+            escape(suite),
+            cases.len(),
+            cases.values().filter(|lines| is_failure(lines)).count()
+        );
+        for (check, lines) in cases {
+            testcase(&mut out, suite, check, lines);
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>");
+    out
+}
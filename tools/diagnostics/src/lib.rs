@@ -1,10 +1,30 @@
-use console::style;
+mod diagnostic;
+mod json;
+mod junit;
+mod sarif;
+mod snippet;
+mod subscriber;
+
+pub use diagnostic::{Diagnostic, Location};
+pub use subscriber::Verbosity;
+
+use console::{Style, style};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum LogFormat {
     #[default]
     Human,
     GitHub,
+    /// Collects all diagnostics into a single SARIF 2.1.0 document, written to stdout at the end
+    /// of the run.
+    Sarif,
+    /// Prints one JSON object per diagnostic (JSON Lines) to stdout.
+    Json,
+    /// Collects all diagnostics into a JUnit XML report with one test case per FIR and check,
+    /// written to stdout at the end of the run.
+    Junit,
 }
 
 impl LogFormat {
@@ -13,12 +33,15 @@ impl LogFormat {
         match self {
             LogFormat::Human => "human",
             LogFormat::GitHub => "github",
+            LogFormat::Sarif => "sarif",
+            LogFormat::Json => "json",
+            LogFormat::Junit => "junit",
         }
     }
 
     #[must_use]
     pub const fn variants() -> &'static [&'static str] {
-        &["human", "github"]
+        &["human", "github", "sarif", "json", "junit"]
     }
 }
 
@@ -34,6 +57,9 @@ impl std::str::FromStr for LogFormat {
         match s.to_ascii_lowercase().as_str() {
             "human" => Ok(LogFormat::Human),
             "github" | "gh" => Ok(LogFormat::GitHub),
+            "sarif" => Ok(LogFormat::Sarif),
+            "json" | "jsonl" => Ok(LogFormat::Json),
+            "junit" => Ok(LogFormat::Junit),
             other => Err(format!(
                 "invalid diagnostics format '{other}'. expected one of: {}",
                 Self::variants().join(", ")
@@ -42,14 +68,76 @@ impl std::str::FromStr for LogFormat {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+
+    #[must_use]
+    pub const fn variants() -> &'static [&'static str] {
+        &["info", "warning", "error"]
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "warning" | "warn" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            other => Err(format!(
+                "invalid severity '{other}'. expected one of: {}",
+                Self::variants().join(", ")
+            )),
+        }
+    }
+}
+
 pub struct Logger {
     format: LogFormat,
+    /// Output buffered until [`Logger::flush`] for formats producing a single document.
+    buffer: Mutex<Buffer>,
+}
+
+#[derive(Default)]
+struct Buffer {
+    diagnostics: Vec<Diagnostic>,
+    notifications: Vec<(Severity, String)>,
+    firs: Vec<String>,
+    checks: Vec<String>,
+    /// Codes of all diagnostics printed in human format, to point at `vacs-data explain`.
+    codes: BTreeSet<String>,
+    /// Whether the run was aborted before completing, e.g. because the dataset could not be
+    /// loaded. Findings alone do not abort a run.
+    aborted: bool,
 }
 
 impl Logger {
     #[must_use]
     pub fn new(format: LogFormat) -> Self {
-        Self { format }
+        Self {
+            format,
+            buffer: Mutex::default(),
+        }
     }
 
     #[must_use]
@@ -58,68 +146,256 @@ impl Logger {
     }
 
     pub fn info(&self, message: impl std::fmt::Display) {
-        match self.format {
-            LogFormat::Human => eprintln!("{message}"),
-            LogFormat::GitHub => println!("{message}"),
-        }
+        tracing::info!("{message}");
     }
 
     pub fn warn(&self, message: impl std::fmt::Display) {
-        match self.format {
-            LogFormat::Human => {
-                eprintln!("{} {}", style("warning:").yellow().bold(), message);
-            }
-            LogFormat::GitHub => println!("::warning::{message}"),
-        }
+        tracing::warn!("{message}");
+        self.notify(Severity::Warning, message);
     }
 
     pub fn error(&self, message: impl std::fmt::Display) {
-        match self.format {
-            LogFormat::Human => eprintln!("{} {}", style("error:").red().bold(), message),
-            LogFormat::GitHub => println!("::error::{message}"),
-        }
+        tracing::error!("{message}");
+        self.notify(Severity::Error, message);
     }
 
     pub fn error_with_context(&self, context: &[String], message: impl std::fmt::Display) {
+        self.emit(
+            &Diagnostic::new(Severity::Error, message.to_string()).with_context(context.to_vec()),
+        );
+    }
+
+    pub fn emit(&self, diagnostic: &Diagnostic) {
+        let Diagnostic {
+            severity,
+            code,
+            rule,
+            context,
+            location,
+            label,
+            help,
+            message,
+            ..
+        } = diagnostic;
+
+        // Console output honors the verbosity, the machine-readable reports are always complete.
+        let enabled = match severity {
+            Severity::Info => tracing::enabled!(tracing::Level::INFO),
+            Severity::Warning => tracing::enabled!(tracing::Level::WARN),
+            Severity::Error => tracing::enabled!(tracing::Level::ERROR),
+        };
+        if !enabled && matches!(self.format, LogFormat::Human | LogFormat::GitHub) {
+            return;
+        }
+
         match self.format {
             LogFormat::Human => {
-                let context_str = if context.is_empty() {
-                    String::new()
-                } else {
-                    format!(
-                        "[{}]",
-                        context
-                            .iter()
-                            .map(|c| style(c).cyan().to_string())
-                            .collect::<Vec<_>>()
-                            .join(" > ")
-                    )
+                let header = match code.as_ref().or(rule.as_ref()) {
+                    Some(code) => format!("{severity}[{code}]:"),
+                    None => format!("{severity}:"),
+                };
+                if let Some(code) = code {
+                    self.buffer().codes.insert(code.clone());
+                }
+                let accent = match severity {
+                    Severity::Info => Style::new().blue().bold(),
+                    Severity::Warning => Style::new().yellow().bold(),
+                    Severity::Error => Style::new().red().bold(),
+                };
+
+                let help_line = |indent: &str| {
+                    if let Some(help) = help {
+                        eprintln!("{indent} = {} {help}", style("help:").bold());
+                    }
+                    if let (Some(_), Some(rule)) = (code, rule) {
+                        eprintln!(
+                            "{indent} = {} reported by rule `{rule}`",
+                            style("note:").bold()
+                        );
+                    }
                 };
-                eprintln!(
-                    "{}{} {}",
-                    style("error:").red().bold(),
-                    context_str,
-                    message
-                );
+
+                let Some(location) = location else {
+                    let context_str = if context.is_empty() {
+                        String::new()
+                    } else {
+                        format!(
+                            "[{}] ",
+                            context
+                                .iter()
+                                .map(|c| style(c).cyan().to_string())
+                                .collect::<Vec<_>>()
+                                .join(" > ")
+                        )
+                    };
+                    eprintln!("{} {context_str}{message}", accent.apply_to(header));
+                    help_line(" ");
+                    return;
+                };
+
+                // The location replaces the context chain, the snippet shows the offending entry.
+                let indent = " ".repeat(location.line.to_string().len());
+                eprintln!("{} {message}", accent.apply_to(header));
+                eprintln!("{indent}{} {location}", style("-->").blue().bold());
+                if let Some(snippet) = snippet::render(location, label.as_deref(), &accent) {
+                    eprintln!("{snippet}");
+                }
+                help_line(&indent);
+                eprintln!();
             }
             LogFormat::GitHub => {
-                let msg = if context.is_empty() {
-                    message.to_string()
+                let command = match severity {
+                    Severity::Info => "notice",
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                };
+
+                let mut properties = Vec::new();
+                if let Some(location) = location {
+                    properties.push(format!(
+                        "file={}",
+                        escape_property(&location.file.to_string_lossy().replace('\\', "/"))
+                    ));
+                    if location.has_span() {
+                        properties.push(format!("line={}", location.line));
+                        properties.push(format!("col={}", location.column));
+                        properties.push(format!("endLine={}", location.end_line));
+                        properties.push(format!("endColumn={}", location.end_column));
+                    }
+                }
+                let title = match (code, rule) {
+                    (Some(code), Some(rule)) => Some(format!("{code} {rule}")),
+                    (Some(title), None) | (None, Some(title)) => Some(title.clone()),
+                    (None, None) => None,
+                };
+                if let Some(title) = title {
+                    properties.push(format!("title={}", escape_property(&title)));
+                }
+
+                let mut msg = if context.is_empty() {
+                    message.clone()
                 } else {
                     format!("{}: {}", context.join(" > "), message)
                 };
-                println!("::error::{msg}");
+                if let Some(help) = help {
+                    msg.push_str(&format!("\nhelp: {help}"));
+                }
+                if properties.is_empty() {
+                    println!("::{command}::{}", escape_data(&msg));
+                } else {
+                    println!(
+                        "::{command} {}::{}",
+                        properties.join(","),
+                        escape_data(&msg)
+                    );
+                }
+            }
+            LogFormat::Json => println!("{}", json::diagnostic(diagnostic)),
+            LogFormat::Sarif | LogFormat::Junit => {
+                self.buffer().diagnostics.push(diagnostic.clone());
+            }
+        }
+    }
+
+    /// Declares the FIRs and checks of a run, so formats listing checks can also report the
+    /// ones that passed.
+    pub fn declare_checks(&self, firs: &[String], checks: &[&str]) {
+        let mut buffer = self.buffer();
+        buffer.firs = firs.to_vec();
+        buffer.checks = checks.iter().map(ToString::to_string).collect();
+    }
+
+    /// Marks the run as aborted, reported as unsuccessful execution by formats supporting it.
+    pub fn abort(&self) {
+        self.buffer().aborted = true;
+    }
+
+    /// Writes all buffered output. Must be called once at the end of the run, also before
+    /// exiting early.
+    pub fn flush(&self) {
+        let buffer = std::mem::take(&mut *self.buffer());
+        match self.format {
+            LogFormat::Sarif => {
+                let log =
+                    sarif::render(&buffer.diagnostics, &buffer.notifications, !buffer.aborted);
+                match serde_json::to_string_pretty(&log) {
+                    Ok(json) => println!("{json}"),
+                    Err(err) => eprintln!("error: failed to serialize SARIF log: {err}"),
+                }
+            }
+            LogFormat::Human => {
+                if let Some(first) = buffer.codes.first()
+                    && tracing::enabled!(tracing::Level::INFO)
+                {
+                    let codes = buffer.codes.iter().cloned().collect::<Vec<_>>().join(", ");
+                    eprintln!(
+                        "Some diagnostics have detailed explanations: {codes}.\nFor more information about a diagnostic, try `vacs-data explain {first}`."
+                    );
+                }
             }
+            LogFormat::Junit => println!(
+                "{}",
+                junit::render(
+                    &buffer.diagnostics,
+                    &buffer.notifications,
+                    &buffer.firs,
+                    &buffer.checks
+                )
+            ),
+            _ => {}
         }
     }
+
+    /// Adds a plain warning or error to the machine-readable reports.
+    fn notify(&self, severity: Severity, message: impl std::fmt::Display) {
+        match self.format {
+            LogFormat::Human | LogFormat::GitHub => {}
+            LogFormat::Json => println!("{}", json::message(severity, &message.to_string())),
+            LogFormat::Sarif | LogFormat::Junit => self
+                .buffer()
+                .notifications
+                .push((severity, message.to_string())),
+        }
+    }
+
+    fn buffer(&self) -> std::sync::MutexGuard<'_, Buffer> {
+        self.buffer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Escapes the message of a GitHub workflow command.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value of a GitHub workflow command.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
 }
 
 use std::sync::OnceLock;
 
 static LOGGER: OnceLock<Logger> = OnceLock::new();
 
-pub fn init(format: LogFormat) {
+/// Initializes the global logger and installs the `tracing` subscriber printing log events.
+pub fn init(format: LogFormat, verbosity: Verbosity) {
     let _ = LOGGER.set(Logger::new(format));
+    subscriber::install(format, verbosity);
+}
+
+/// Marks the run as aborted, see [`Logger::abort`].
+pub fn abort() {
+    logger().abort();
+}
+
+/// Writes all output buffered by the global logger, see [`Logger::flush`].
+pub fn flush() {
+    logger().flush();
 }
 
 fn logger() -> &'static Logger {
@@ -145,6 +421,14 @@ pub mod log {
         logger().error_with_context(context, message);
     }
 
+    pub fn emit(diagnostic: &super::Diagnostic) {
+        logger().emit(diagnostic);
+    }
+
+    pub fn declare_checks(firs: &[String], checks: &[&str]) {
+        logger().declare_checks(firs, checks);
+    }
+
     #[must_use]
     pub fn is_human() -> bool {
         logger().is_human()
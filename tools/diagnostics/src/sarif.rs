@@ -0,0 +1,111 @@
+use crate::{Diagnostic, Location, Severity};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+const fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "note",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+fn message(diagnostic: &Diagnostic) -> String {
+    let mut message = if diagnostic.context.is_empty() {
+        diagnostic.message.clone()
+    } else {
+        format!("{}: {}", diagnostic.context.join(" > "), diagnostic.message)
+    };
+    if let Some(help) = &diagnostic.help {
+        message.push_str(&format!("\nhelp: {help}"));
+    }
+    message
+}
+
+fn artifact_location(location: &Location) -> Value {
+    let path = location.file.to_string_lossy().replace('\\', "/");
+    if location.file.is_absolute() {
+        json!({ "uri": format!("file://{}{path}", if path.starts_with('/') { "" } else { "/" }) })
+    } else {
+        json!({ "uri": path, "uriBaseId": "%SRCROOT%" })
+    }
+}
+
+fn result(diagnostic: &Diagnostic) -> Value {
+    let mut result = json!({
+        "level": level(diagnostic.severity),
+        "message": { "text": message(diagnostic) },
+    });
+    if let Some(id) = diagnostic.code.as_ref().or(diagnostic.rule.as_ref()) {
+        result["ruleId"] = json!(id);
+    }
+    if let Some(location) = &diagnostic.location {
+        let mut physical = json!({ "artifactLocation": artifact_location(location) });
+        if location.has_span() {
+            physical["region"] = json!({
+                "startLine": location.line,
+                "startColumn": location.column,
+                "endLine": location.end_line,
+                // SARIF end columns are exclusive.
+                "endColumn": location.end_column + 1,
+            });
+        }
+        result["locations"] = json!([{ "physicalLocation": physical }]);
+    }
+    if let Some(label) = &diagnostic.label {
+        result["properties"] = json!({ "label": label });
+    }
+    result
+}
+
+/// Builds a SARIF 2.1.0 log from all diagnostics and plain log messages of a run. A run is
+/// successful if it completed, regardless of its findings.
+pub(crate) fn render(
+    diagnostics: &[Diagnostic],
+    notifications: &[(Severity, String)],
+    successful: bool,
+) -> Value {
+    // Rules are identified by their stable code, the lint rule ID becomes the readable name.
+    let rules = diagnostics
+        .iter()
+        .filter_map(|d| match (&d.code, &d.rule) {
+            (Some(code), rule) => Some((code, rule.as_ref())),
+            (None, Some(rule)) => Some((rule, None)),
+            (None, None) => None,
+        })
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .map(|(id, name)| match name {
+            Some(name) => json!({ "id": id, "name": name }),
+            None => json!({ "id": id }),
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "vacs-data",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                },
+            },
+            "invocations": [{
+                "executionSuccessful": successful,
+                "toolExecutionNotifications": notifications
+                    .iter()
+                    .map(|(severity, text)| json!({
+                        "level": level(*severity),
+                        "message": { "text": text },
+                    }))
+                    .collect::<Vec<_>>(),
+            }],
+            "results": diagnostics.iter().map(result).collect::<Vec<_>>(),
+        }],
+    })
+}
@@ -0,0 +1,62 @@
+use crate::Location;
+use console::{Style, style};
+
+const TAB_WIDTH: usize = 4;
+
+/// Renders the source lines of a location, underlining the span with carets, e.g.
+///
+/// ```text
+///    |
+/// 12 |             "station_id": "ESAA FMP"
+///    |                           ^^^^^^^^^^ station does not exist
+/// ```
+///
+/// Returns `None` if the location has no span, the file cannot be read or the location is out of
+/// range. Spans covering several lines are only underlined up to the end of their first line.
+#[must_use]
+pub fn render(location: &Location, label: Option<&str>, accent: &Style) -> Option<String> {
+    if !location.has_span() {
+        return None;
+    }
+    let content = std::fs::read_to_string(&location.file).ok()?;
+    let line = content.lines().nth(location.line.checked_sub(1)?)?;
+
+    let start = location.column.saturating_sub(1);
+    let end = if location.end_line == location.line {
+        location.end_column.max(location.column)
+    } else {
+        line.chars().count()
+    };
+
+    let mut text = String::new();
+    let mut offset = 0;
+    let mut width = 0;
+    for (idx, c) in line.chars().enumerate() {
+        let w = if c == '\t' { TAB_WIDTH } else { 1 };
+        if c == '\t' {
+            text.push_str(&" ".repeat(TAB_WIDTH));
+        } else {
+            text.push(c);
+        }
+        if idx < start {
+            offset += w;
+        } else if idx < end {
+            width += w;
+        }
+    }
+    let text = text.trim_end();
+
+    let number = location.line.to_string();
+    let gutter = " ".repeat(number.len());
+    let bar = style("|").blue().bold();
+    let carets = accent.apply_to("^".repeat(width.max(1)));
+    let label = label
+        .map(|l| format!(" {}", accent.apply_to(l)))
+        .unwrap_or_default();
+
+    Some(format!(
+        "{gutter} {bar}\n{} {bar} {text}\n{gutter} {bar} {}{carets}{label}",
+        style(number).blue().bold(),
+        " ".repeat(offset),
+    ))
+}
@@ -0,0 +1,107 @@
+use crate::LogFormat;
+use console::style;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// How much is logged, set by the `-q` and `-v` flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// Errors only.
+    Quiet,
+    #[default]
+    Normal,
+    /// Additionally logs every file read or written.
+    Verbose,
+    /// Additionally logs decisions about single entities, e.g. skipped positions.
+    Trace,
+}
+
+impl Verbosity {
+    #[must_use]
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Trace,
+        }
+    }
+
+    const fn level(self) -> LevelFilter {
+        match self {
+            Verbosity::Quiet => LevelFilter::ERROR,
+            Verbosity::Normal => LevelFilter::INFO,
+            Verbosity::Verbose => LevelFilter::DEBUG,
+            Verbosity::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Formats log events like the previous `eprintln!`-based logger did, without timestamps or
+/// targets.
+struct EventFormat {
+    format: LogFormat,
+}
+
+impl<S, N> FormatEvent<S, N> for EventFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let level = *event.metadata().level();
+        match self.format {
+            LogFormat::Human => match level {
+                Level::ERROR => write!(writer, "{} ", style("error:").red().bold())?,
+                Level::WARN => write!(writer, "{} ", style("warning:").yellow().bold())?,
+                Level::INFO => {}
+                Level::DEBUG => write!(writer, "{} ", style("debug:").dim())?,
+                Level::TRACE => write!(writer, "{} ", style("trace:").dim())?,
+            },
+            LogFormat::GitHub => match level {
+                Level::ERROR => write!(writer, "::error::")?,
+                Level::WARN => write!(writer, "::warning::")?,
+                Level::INFO => {}
+                Level::DEBUG | Level::TRACE => write!(writer, "::debug::")?,
+            },
+            LogFormat::Sarif | LogFormat::Json | LogFormat::Junit => {
+                if level != Level::INFO {
+                    write!(writer, "{}: ", level.as_str().to_ascii_lowercase())?;
+                }
+            }
+        }
+        ctx.field_format().format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
+
+/// Installs the global `tracing` subscriber. The `RUST_LOG` environment variable can add
+/// directives on top of the verbosity, e.g. `RUST_LOG=vacs_data_importer=trace`.
+pub(crate) fn install(format: LogFormat, verbosity: Verbosity) {
+    let directives = std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_default();
+    let filter = EnvFilter::builder().parse_lossy(format!("{},{directives}", verbosity.level()));
+
+    // GitHub workflow commands must be written to stdout, the machine-readable formats keep
+    // stdout for their report.
+    let writer = match format {
+        LogFormat::GitHub => BoxMakeWriter::new(std::io::stdout),
+        _ => BoxMakeWriter::new(std::io::stderr),
+    };
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(false)
+        .event_format(EventFormat { format })
+        .try_init();
+}
@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+use vacs_data_validator::codes;
+
+/// Errors returned by the importers. None of them are logged by the importer itself, so
+/// callers decide how to report them.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("input {0:?} does not exist")]
+    InputNotFound(PathBuf),
+
+    #[error("input {0:?} is not a directory")]
+    InputNotDirectory(PathBuf),
+
+    #[error("output {0:?} is not a directory")]
+    OutputNotDirectory(PathBuf),
+
+    #[error("failed to create output directory {path:?}: {source}")]
+    CreateOutputDirectory {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error(
+        "{label} output file {path:?} already exists, either --merge or --overwrite must be specified"
+    )]
+    OutputExists { label: String, path: PathBuf },
+
+    #[error("failed to read {path:?}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse {path:?}: {source}")]
+    ParseToml {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    #[error("failed to parse {path:?}: {source}")]
+    ParseJson {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("failed to convert input to {label}: {message}")]
+    Convert { label: String, message: String },
+
+    #[error("failed to serialize {label}: {message}")]
+    Serialize { label: String, message: String },
+
+    #[error("failed to write {label} output file {path:?}: {source}")]
+    Write {
+        label: String,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("FIR {0} does not exist in dataset")]
+    UnknownFir(String),
+
+    #[error(transparent)]
+    Validator(#[from] vacs_data_validator::Error),
+}
+
+impl Error {
+    /// Stable code explained by `vacs-data explain`.
+    #[must_use]
+    pub const fn code(&self) -> Option<&'static str> {
+        match self {
+            Error::InputNotFound(_) => Some(codes::INPUT_NOT_FOUND),
+            Error::InputNotDirectory(_) => Some(codes::INPUT_NOT_DIRECTORY),
+            Error::OutputNotDirectory(_)
+            | Error::CreateOutputDirectory { .. }
+            | Error::Serialize { .. }
+            | Error::Write { .. } => Some(codes::WRITE_FAILED),
+            Error::OutputExists { .. } => Some(codes::OUTPUT_EXISTS),
+            Error::Read { .. } => Some(codes::UNREADABLE_FILE),
+            Error::ParseToml { .. } | Error::ParseJson { .. } => Some(codes::INVALID_FILE),
+            Error::Convert { .. } => Some(codes::CONVERSION_FAILED),
+            Error::UnknownFir(_) => Some(codes::UNKNOWN_FIR),
+            Error::Validator(err) => err.code(),
+        }
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
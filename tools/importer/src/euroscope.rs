@@ -16,7 +16,7 @@ pub fn parse(
     overwrite: bool,
     merge: bool,
     format: crate::OutputFormat,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> crate::Result<()> {
     log::info(format_args!(
         "Parsing EuroScope sectorfile data from {input:?} to {output:?}"
     ));
@@ -33,13 +33,7 @@ pub fn parse(
         merge,
     )?;
 
-    let file = match std::fs::File::open(input) {
-        Ok(f) => f,
-        Err(err) => {
-            log::error(format_args!("Failed to open input file {input:?}: {err:?}"));
-            return Err(err.into());
-        }
-    };
+    let file = crate::open_input(input)?;
 
     let decoder = DecodeReaderBytesBuilder::new()
         .encoding(Some(WINDOWS_1252))
@@ -70,23 +64,28 @@ pub fn parse(
         }
         // Ignore positions outside specified prefixes
         if !prefixes.is_empty() && prefixes.iter().all(|p| !trimmed.starts_with(p)) {
+            tracing::trace!("Skipping position outside of prefixes: {trimmed}");
             continue;
         }
 
-        if let Ok(position) = PositionRaw::from_ese_line(trimmed) {
-            if position.facility_type == FacilityType::Unknown {
-                continue;
+        match PositionRaw::from_ese_line(trimmed) {
+            Ok(position) if position.facility_type == FacilityType::Unknown => {
+                tracing::trace!(
+                    "Skipping position `{}` with unknown facility type",
+                    position.id
+                );
+            }
+            Ok(position) => {
+                tracing::trace!("Adding position `{}`", position.id);
+                positions.push(position);
             }
-            positions.push(position);
+            Err(err) => tracing::trace!("Skipping invalid position line ({err}): {trimmed}"),
         }
     }
 
     if merge && output_positions.exists() {
-        log::info(format_args!(
-            "Reading existing positions from {output_positions:?}"
-        ));
-        let content = std::fs::read_to_string(&output_positions)?;
-        let mut existing_config: PositionConfigFile = toml::from_str(&content)?;
+        let mut existing_config: PositionConfigFile =
+            crate::read_existing(&output_positions, "positions")?;
         let existing_ids: HashSet<_> = existing_config
             .positions
             .iter()
@@ -112,13 +111,7 @@ pub fn parse(
     });
 
     let serialized_positions =
-        match crate::format::serialize(&PositionConfigFile { positions }, format) {
-            Ok(s) => s,
-            Err(err) => {
-                log::error(format_args!("Failed to serialize positions: {err:?}"));
-                return Err(err);
-            }
-        };
+        crate::format::serialize(&PositionConfigFile { positions }, format, "positions")?;
 
     crate::write_output_file(&output_positions, &serialized_positions, "Positions")?;
 
@@ -54,9 +54,14 @@ impl OutputFormat {
 pub fn serialize<T: Serialize>(
     value: &T,
     format: OutputFormat,
-) -> Result<String, Box<dyn std::error::Error>> {
-    match format {
-        OutputFormat::Toml => Ok(toml::to_string_pretty(value)?),
-        OutputFormat::Json => Ok(serde_json::to_string_pretty(value)?),
-    }
+    label: &str,
+) -> crate::Result<String> {
+    let result = match format {
+        OutputFormat::Toml => toml::to_string_pretty(value).map_err(|err| err.to_string()),
+        OutputFormat::Json => serde_json::to_string_pretty(value).map_err(|err| err.to_string()),
+    };
+    result.map_err(|message| crate::Error::Serialize {
+        label: label.to_string(),
+        message,
+    })
 }
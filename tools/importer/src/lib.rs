@@ -1,31 +1,32 @@
+mod error;
 pub mod euroscope;
 pub mod format;
+pub mod snapshots;
 pub mod vatglasses;
 
+pub use error::{Error, Result};
 pub use format::OutputFormat;
 
 use std::path::{Path, PathBuf};
 use vacs_data_diagnostics::log;
 
-pub fn check_input_exists(input: &Path) -> Result<(), Box<dyn std::error::Error>> {
+pub fn check_input_exists(input: &Path) -> Result<()> {
     if !input.exists() {
-        log::error(format_args!("Input file {input:?} does not exist"));
-        return Err("Input file does not exist".into());
+        return Err(Error::InputNotFound(input.to_path_buf()));
     }
     Ok(())
 }
 
-pub fn ensure_output_directory(output: &Path) -> Result<(), Box<dyn std::error::Error>> {
+pub fn ensure_output_directory(output: &Path) -> Result<()> {
     if output.exists() {
         if !output.is_dir() {
-            log::error(format_args!("Output {output:?} is not a directory"));
-            return Err("Output is not a directory".into());
+            return Err(Error::OutputNotDirectory(output.to_path_buf()));
         }
-    } else if let Err(err) = std::fs::create_dir_all(output) {
-        log::error(format_args!(
-            "Failed to create output directory {output:?}: {err:?}",
-        ));
-        return Err(err.into());
+    } else {
+        std::fs::create_dir_all(output).map_err(|source| Error::CreateOutputDirectory {
+            path: output.to_path_buf(),
+            source,
+        })?;
     }
     Ok(())
 }
@@ -36,7 +37,7 @@ pub fn check_output_file(
     label: &str,
     overwrite: bool,
     merge: bool,
-) -> Result<PathBuf, Box<dyn std::error::Error>> {
+) -> Result<PathBuf> {
     let output_path = output_dir.join(filename);
     if output_path.exists() {
         if overwrite {
@@ -48,25 +49,42 @@ pub fn check_output_file(
                 "Merging with existing {label} output file {output_path:?}"
             ));
         } else {
-            log::error(format_args!(
-                "{label} output file {output_path:?} already exists. Either --merge or --overwrite must be specified."
-            ));
-            return Err(format!("{label} output file already exists").into());
+            return Err(Error::OutputExists {
+                label: label.to_string(),
+                path: output_path,
+            });
         }
     }
     Ok(output_path)
 }
 
-pub fn write_output_file(
-    path: &Path,
-    content: &str,
-    label: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    if let Err(err) = std::fs::write(path, content) {
-        log::error(format_args!(
-            "Failed to write {label} output file {path:?}: {err:?}"
-        ));
-        return Err(err.into());
-    }
-    Ok(())
+pub fn write_output_file(path: &Path, content: &str, label: &str) -> Result<()> {
+    tracing::debug!("Writing {label} output file {path:?}");
+    std::fs::write(path, content).map_err(|source| Error::Write {
+        label: label.to_string(),
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Opens an input file for reading.
+fn open_input(path: &Path) -> Result<std::fs::File> {
+    tracing::debug!("Reading {path:?}");
+    std::fs::File::open(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Reads an existing output file to merge new entries into.
+fn read_existing<T: serde::de::DeserializeOwned>(path: &Path, label: &str) -> Result<T> {
+    log::info(format_args!("Reading existing {label} from {path:?}"));
+    let content = std::fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    toml::from_str(&content).map_err(|source| Error::ParseToml {
+        path: path.to_path_buf(),
+        source,
+    })
 }
@@ -0,0 +1,235 @@
+use console::style;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use vacs_data_diagnostics::log;
+use vacs_data_validator::coverage::{Login, PositionMatch, match_position};
+use vacs_data_validator::dataset::{Dataset, Fir};
+use vacs_protocol::vatsim::PositionId;
+use vacs_vatsim::FacilityType;
+use vacs_vatsim::coverage::position::{PositionConfigFile, PositionRaw};
+
+/// An unmatched login, identified by callsign, frequency and facility type.
+type LoginKey = (String, String, FacilityType);
+
+pub fn analyze(
+    input: &PathBuf,
+    dataset: &PathBuf,
+    fir: &str,
+    prefixes: &[String],
+    format: crate::OutputFormat,
+) -> crate::Result<()> {
+    log::info(format_args!(
+        "Analyzing unmatched logins in snapshots from {input:?} against {dataset:?}"
+    ));
+
+    crate::check_input_exists(input)?;
+    if !input.is_dir() {
+        return Err(crate::Error::InputNotDirectory(input.clone()));
+    }
+
+    let dataset = Dataset::load(dataset)?;
+    let Some(fir) = dataset.fir(fir) else {
+        return Err(crate::Error::UnknownFir(fir.to_string()));
+    };
+
+    let mut snapshots = std::fs::read_dir(input)
+        .map_err(|source| crate::Error::Read {
+            path: input.clone(),
+            source,
+        })?
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    snapshots.sort();
+
+    let mut unmatched = BTreeMap::new();
+    let mut skipped = 0;
+    for snapshot in &snapshots {
+        let logins = match vacs_data_validator::datafeed::read_snapshot(snapshot) {
+            Ok(logins) => logins,
+            Err(err) => {
+                log::warn(format_args!("Skipping snapshot {snapshot:?}: {err}"));
+                skipped += 1;
+                continue;
+            }
+        };
+        count_unmatched(&dataset, fir, prefixes, logins, &mut unmatched);
+    }
+
+    log::info(format_args!(
+        "Found {} unmatched logins for FIR {} in {} snapshots",
+        unmatched.len(),
+        fir.id,
+        snapshots.len() - skipped
+    ));
+
+    let mut entries = unmatched.into_iter().collect::<Vec<_>>();
+    entries.sort_by(|(a_key, a_count), (b_key, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_key.cmp(b_key))
+    });
+
+    let mut new_positions: Vec<PositionRaw> = Vec::new();
+    let mut proposed_ids = HashSet::new();
+    for ((callsign, frequency, facility_type), count) in entries {
+        let login = Login::new(&callsign, &frequency, facility_type);
+        let prefix = callsign_prefix(&callsign);
+        let candidates = prefix_candidates(fir, &login);
+
+        println!(
+            "Login {} ({frequency} {}) seen {count} times did not match any position",
+            style(format!("`{callsign}`")).cyan(),
+            facility_type.as_str()
+        );
+
+        if candidates.is_empty() {
+            if proposed_ids.insert(callsign.clone()) {
+                new_positions.push(PositionRaw {
+                    id: PositionId::from(callsign.clone()),
+                    frequency,
+                    prefixes: HashSet::from([prefix.to_string()]),
+                    facility_type: login.facility_type,
+                    profile_id: None,
+                });
+            }
+            println!("  proposal: add new position `{callsign}`");
+        } else {
+            for candidate in candidates {
+                println!(
+                    "  proposal: add prefix \"{prefix}\" to position `{}`",
+                    candidate.id
+                );
+            }
+        }
+    }
+
+    if !new_positions.is_empty() {
+        new_positions.sort_by(|a, b| {
+            a.facility_type
+                .cmp(&b.facility_type)
+                .reverse()
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        let serialized = crate::format::serialize(
+            &PositionConfigFile {
+                positions: new_positions,
+            },
+            format,
+            "positions",
+        )?;
+        println!("Proposed positions:\n{serialized}");
+    }
+
+    Ok(())
+}
+
+/// Counts the logins belonging to a FIR that do not match any position of the dataset. Without
+/// explicit prefixes, a login belongs to the FIR if its callsign starts with the FIR's country
+/// code, so logins from airports without any position yet are counted as well.
+fn count_unmatched(
+    dataset: &Dataset,
+    fir: &Fir,
+    prefixes: &[String],
+    logins: impl IntoIterator<Item = Login>,
+    unmatched: &mut BTreeMap<LoginKey, usize>,
+) {
+    let prefixes = if prefixes.is_empty() {
+        vec![fir.country_code().to_ascii_uppercase()]
+    } else {
+        prefixes.iter().map(|p| p.to_ascii_uppercase()).collect()
+    };
+    for login in logins {
+        if !prefixes
+            .iter()
+            .any(|p| login.callsign.starts_with(p.as_str()))
+        {
+            continue;
+        }
+        if let PositionMatch::None = match_position(dataset.positions(), &login) {
+            let key = (login.callsign, login.frequency, login.facility_type);
+            *unmatched.entry(key).or_default() += 1;
+        }
+    }
+}
+
+/// Returns the ICAO part of a callsign, e.g. `EFHK` for `EFHK_E_TWR`.
+fn callsign_prefix(callsign: &str) -> &str {
+    callsign.split('_').next().unwrap_or(callsign)
+}
+
+/// Returns the FIR's positions that would have matched the login if they had an additional
+/// callsign prefix.
+fn prefix_candidates<'a>(fir: &'a Fir, login: &Login) -> Vec<&'a PositionRaw> {
+    fir.positions
+        .iter()
+        .filter(|p| p.frequency == login.frequency && p.facility_type == login.facility_type)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset() -> Dataset {
+        Dataset {
+            firs: vec![Fir {
+                id: "EF".to_string(),
+                path: "EF".into(),
+                stations: Vec::new(),
+                positions: vec![
+                    toml::from_str(
+                        "id = \"EFHK_TWR\"\nprefixes = [\"EFHK\"]\nfrequency = \"118.600\"\nfacility_type = \"TWR\"",
+                    )
+                    .unwrap(),
+                ],
+            }],
+        }
+    }
+
+    fn logins(logins: &[&str]) -> Vec<Login> {
+        logins.iter().map(|login| login.parse().unwrap()).collect()
+    }
+
+    fn callsigns(unmatched: &BTreeMap<LoginKey, usize>) -> Vec<(&str, usize)> {
+        unmatched
+            .iter()
+            .map(|((callsign, ..), count)| (callsign.as_str(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn counts_logins_from_airports_without_positions() {
+        let dataset = dataset();
+        let snapshot = logins(&[
+            "EFHK_TWR:118.600:TWR",
+            "EFXX_TWR:118.000:TWR",
+            "ESSA_TWR:118.500:TWR",
+        ]);
+        let mut unmatched = BTreeMap::new();
+        for _ in 0..2 {
+            count_unmatched(
+                &dataset,
+                &dataset.firs[0],
+                &[],
+                snapshot.clone(),
+                &mut unmatched,
+            );
+        }
+        assert_eq!(callsigns(&unmatched), [("EFXX_TWR", 2)]);
+    }
+
+    #[test]
+    fn explicit_prefixes_replace_the_country_code() {
+        let dataset = dataset();
+        let mut unmatched = BTreeMap::new();
+        count_unmatched(
+            &dataset,
+            &dataset.firs[0],
+            &["essa".to_string()],
+            logins(&["EFXX_TWR:118.000:TWR", "ESSA_TWR:118.500:TWR"]),
+            &mut unmatched,
+        );
+        assert_eq!(callsigns(&unmatched), [("ESSA_TWR", 1)]);
+    }
+}
@@ -14,7 +14,7 @@ pub fn parse(
     overwrite: bool,
     merge: bool,
     format: crate::OutputFormat,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> crate::Result<()> {
     log::info(format_args!(
         "Parsing VATglasses data from {input:?} to {output:?}"
     ));
@@ -39,42 +39,25 @@ pub fn parse(
         merge,
     )?;
 
-    let file = match std::fs::File::open(input) {
-        Ok(f) => f,
-        Err(err) => {
-            log::error(format_args!("Failed to open input file {input:?}: {err:?}"));
-            return Err(err.into());
-        }
-    };
-
-    let data: VatglassesData = match serde_json::from_reader(file) {
-        Ok(d) => d,
-        Err(err) => {
-            log::error(format_args!(
-                "Failed to parse input file {input:?}: {err:?}"
-            ));
-            return Err(err.into());
-        }
-    };
+    let file = crate::open_input(input)?;
 
-    log::info(format_args!("Parsed VATglasses data: {data:?}"));
+    let data: VatglassesData =
+        serde_json::from_reader(file).map_err(|source| crate::Error::ParseJson {
+            path: input.clone(),
+            source,
+        })?;
 
-    let mut stations = match StationConfigFile::try_from_ref(&data) {
-        Ok(s) => s,
-        Err(err) => {
-            log::error(format_args!(
-                "Failed to convert VATglasses data to stations: {err:?}"
-            ));
-            return Err(err.into());
-        }
-    };
+    tracing::trace!("Parsed VATglasses data: {data:?}");
+
+    let mut stations =
+        StationConfigFile::try_from_ref(&data).map_err(|message| crate::Error::Convert {
+            label: "stations".to_string(),
+            message,
+        })?;
 
     if merge && output_stations.exists() {
-        log::info(format_args!(
-            "Reading existing stations from {output_stations:?}"
-        ));
-        let content = std::fs::read_to_string(&output_stations)?;
-        let mut existing_config: StationConfigFile = toml::from_str(&content)?;
+        let mut existing_config: StationConfigFile =
+            crate::read_existing(&output_stations, "stations")?;
         let existing_ids: HashSet<_> = existing_config
             .stations
             .iter()
@@ -94,32 +77,19 @@ pub fn parse(
 
     stations.stations.sort_by(|a, b| a.id.cmp(&b.id));
 
-    let serialized_stations = match crate::format::serialize(&stations, format) {
-        Ok(s) => s,
-        Err(err) => {
-            log::error(format_args!("Failed to serialize stations: {err:?}"));
-            return Err(err);
-        }
-    };
+    let serialized_stations = crate::format::serialize(&stations, format, "stations")?;
 
     crate::write_output_file(&output_stations, &serialized_stations, "Stations")?;
 
-    let mut positions = match PositionConfigFile::try_from_ref(&data) {
-        Ok(p) => p,
-        Err(err) => {
-            log::error(format_args!(
-                "Failed to convert VATglasses data to positions: {err:?}"
-            ));
-            return Err(err.into());
-        }
-    };
+    let mut positions =
+        PositionConfigFile::try_from_ref(&data).map_err(|message| crate::Error::Convert {
+            label: "positions".to_string(),
+            message,
+        })?;
 
     if merge && output_positions.exists() {
-        log::info(format_args!(
-            "Reading existing positions from {output_positions:?}"
-        ));
-        let content = std::fs::read_to_string(&output_positions)?;
-        let mut existing_config: PositionConfigFile = toml::from_str(&content)?;
+        let mut existing_config: PositionConfigFile =
+            crate::read_existing(&output_positions, "positions")?;
         let existing_ids: HashSet<_> = existing_config
             .positions
             .iter()
@@ -144,13 +114,7 @@ pub fn parse(
             .then_with(|| a.id.cmp(&b.id))
     });
 
-    let serialized_positions = match crate::format::serialize(&positions, format) {
-        Ok(s) => s,
-        Err(err) => {
-            log::error(format_args!("Failed to serialize positions: {err:?}"));
-            return Err(err);
-        }
-    };
+    let serialized_positions = crate::format::serialize(&positions, format, "positions")?;
 
     crate::write_output_file(&output_positions, &serialized_positions, "Positions")?;
 
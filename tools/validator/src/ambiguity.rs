@@ -0,0 +1,103 @@
+use crate::dataset::Dataset;
+use std::collections::{BTreeMap, BTreeSet};
+use vacs_vatsim::coverage::position::PositionRaw;
+
+/// A set of positions that a single non-exact callsign can match at the same time, forcing vacs
+/// to prompt the user for a selection.
+#[derive(Debug)]
+pub struct AmbiguousGroup<'a> {
+    pub frequency: &'a str,
+    pub positions: Vec<&'a PositionRaw>,
+    /// Callsign prefix for which all positions of the group match.
+    pub prefix: &'a str,
+    /// Prefixes of group members that no other position with the same frequency and facility
+    /// type overlaps with, so callsigns starting with them still resolve to that member alone.
+    pub unique: Vec<(&'a str, &'a PositionRaw)>,
+}
+
+/// Whether one prefix is a prefix of the other, so some callsign starts with both.
+fn overlaps(a: &str, b: &str) -> bool {
+    let (a, b) = (a.to_ascii_uppercase(), b.to_ascii_uppercase());
+    a.starts_with(&b) || b.starts_with(&a)
+}
+
+/// Finds all maximal groups of positions sharing frequency and facility type whose prefixes
+/// overlap, so a callsign not matching any position ID exactly matches more than one of them.
+#[must_use]
+pub fn find_ambiguous_groups(dataset: &Dataset) -> Vec<AmbiguousGroup<'_>> {
+    let mut buckets: BTreeMap<(&str, &str), Vec<&PositionRaw>> = BTreeMap::new();
+    for position in dataset.positions() {
+        buckets
+            .entry((&position.frequency, position.facility_type.as_str()))
+            .or_default()
+            .push(position);
+    }
+
+    let mut groups = Vec::new();
+    for ((frequency, _), positions) in buckets {
+        if positions.len() < 2 {
+            continue;
+        }
+
+        // A callsign starting with `probe` matches every position having a prefix of `probe`.
+        let mut sets: Vec<(BTreeSet<usize>, &str)> = Vec::new();
+        let probes: BTreeSet<&str> = positions
+            .iter()
+            .flat_map(|p| p.prefixes.iter().map(String::as_str))
+            .collect();
+        for probe in probes {
+            let probe_upper = probe.to_ascii_uppercase();
+            let matching: BTreeSet<usize> = positions
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| {
+                    p.prefixes
+                        .iter()
+                        .any(|prefix| probe_upper.starts_with(&prefix.to_ascii_uppercase()))
+                })
+                .map(|(idx, _)| idx)
+                .collect();
+            if matching.len() > 1 && !sets.iter().any(|(s, _)| s == &matching) {
+                sets.push((matching, probe));
+            }
+        }
+
+        for (set, prefix) in &sets {
+            let is_maximal = !sets
+                .iter()
+                .any(|(other, _)| other.len() > set.len() && other.is_superset(set));
+            if is_maximal {
+                let mut members: Vec<_> = set.iter().map(|idx| positions[*idx]).collect();
+                members.sort_by(|a, b| a.id.cmp(&b.id));
+
+                let mut unique = Vec::new();
+                for member in &members {
+                    let mut prefixes = member
+                        .prefixes
+                        .iter()
+                        .map(String::as_str)
+                        .collect::<Vec<_>>();
+                    prefixes.sort_unstable();
+                    for candidate in prefixes {
+                        let shared = positions.iter().any(|other| {
+                            other.id != member.id
+                                && other.prefixes.iter().any(|p| overlaps(p, candidate))
+                        });
+                        if !shared {
+                            unique.push((candidate, *member));
+                        }
+                    }
+                }
+
+                groups.push(AmbiguousGroup {
+                    frequency,
+                    positions: members,
+                    prefix,
+                    unique,
+                });
+            }
+        }
+    }
+
+    groups
+}
@@ -0,0 +1,629 @@
+//! Stable codes for all diagnostics and errors reported by the tools, explained by
+//! `vacs-data explain`.
+//!
+//! Codes are never reused or renumbered. `VD00xx` codes are dataset errors, `VD01xx` codes are
+//! lint rules and `VD02xx` codes are problems with the tool's inputs and outputs.
+
+use std::fmt::Write;
+
+pub const INVALID_ENTRY: &str = "VD0001";
+pub const DUPLICATE_ID: &str = "VD0002";
+pub const UNKNOWN_POSITION: &str = "VD0003";
+pub const UNKNOWN_PARENT_STATION: &str = "VD0004";
+pub const UNKNOWN_STATION: &str = "VD0005";
+pub const UNKNOWN_PROFILE: &str = "VD0006";
+pub const CIRCULAR_INHERITANCE: &str = "VD0007";
+pub const MISSING_CONTROLLED_BY: &str = "VD0008";
+pub const INVALID_FREQUENCY: &str = "VD0009";
+pub const UNKNOWN_RULE: &str = "VD0010";
+
+pub const AMBIGUOUS_POSITIONS: &str = "VD0100";
+pub const CROSS_FIR_INHERITANCE: &str = "VD0101";
+pub const GEO_BUTTON_TOO_SMALL: &str = "VD0102";
+pub const GEO_PERCENTAGE_SUM: &str = "VD0103";
+pub const GEO_NODE_OVERFLOW: &str = "VD0104";
+
+pub const INPUT_NOT_FOUND: &str = "VD0200";
+pub const INPUT_NOT_DIRECTORY: &str = "VD0201";
+pub const UNKNOWN_FIR: &str = "VD0202";
+pub const UNREADABLE_FILE: &str = "VD0203";
+pub const INVALID_FILE: &str = "VD0204";
+pub const INVALID_LOGIN: &str = "VD0205";
+pub const OUTPUT_EXISTS: &str = "VD0206";
+pub const WRITE_FAILED: &str = "VD0207";
+pub const CONVERSION_FAILED: &str = "VD0208";
+pub const UNKNOWN_STATION_ID: &str = "VD0209";
+pub const UNKNOWN_PROFILE_ID: &str = "VD0210";
+
+/// Long-form explanation of a code.
+#[derive(Debug)]
+pub struct Code {
+    pub code: &'static str,
+    /// Kebab-case name, equal to the rule ID for lint rules.
+    pub name: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    /// Why the problem is reported at all.
+    pub rationale: &'static str,
+    pub examples: &'static [Example],
+}
+
+/// An erroneous snippet of a dataset file and its fixed version.
+#[derive(Debug)]
+pub struct Example {
+    pub caption: &'static str,
+    /// File the snippet belongs to, e.g. `stations.toml`.
+    pub file: &'static str,
+    pub bad: &'static str,
+    pub good: &'static str,
+}
+
+/// All codes, ordered by code.
+pub const CODES: &[Code] = &[
+    Code {
+        code: INVALID_ENTRY,
+        name: "invalid-entry",
+        title: "invalid dataset entry",
+        description: "\
+An entry does not follow the documented dataset format, so the dataset cannot be loaded. The
+message names the offending entry and field. Problems with their own code, such as a missing
+`controlled_by` list (VD0008) or an invalid frequency (VD0009), are not reported as VD0001.
+Other common causes are:
+
+- an empty `prefixes` list, or a `prefixes` or `controlled_by` list containing duplicates
+- a `facility_type` that is not a VATSIM facility type (RMP, DEL, GND, TWR, APP, DEP, CTR, FSS,
+  RDO or FMP)
+- an `id` not starting with the FIR's two-letter country code",
+        rationale: "\
+vacs matches controller logins and resolves coverage from the complete dataset. A single invalid
+entry would make matching or coverage silently wrong, so the dataset is rejected as a whole.",
+        examples: &[Example {
+            caption: "Facility types are VATSIM facility types",
+            file: "positions.toml",
+            bad: r#"[[positions]]
+id = "LOWW_TWR"
+prefixes = ["LOWW"]
+frequency = "119.400"
+facility_type = "TOWER""#,
+            good: r#"[[positions]]
+id = "LOWW_TWR"
+prefixes = ["LOWW"]
+frequency = "119.400"
+facility_type = "TWR""#,
+        }],
+    },
+    Code {
+        code: DUPLICATE_ID,
+        name: "duplicate-id",
+        title: "duplicate ID",
+        description: "\
+Two stations, positions or profiles share the same `id`. IDs must be unique across the entire
+dataset, not only within a FIR directory. The location points at the second definition.",
+        rationale: "\
+References such as `controlled_by`, `parent_id` and `station_id` are resolved by ID. With two
+entries of the same ID it is undefined which one a reference points to.",
+        examples: &[Example {
+            caption: "Merge the definitions or rename one of them",
+            file: "stations.toml",
+            bad: r#"[[stations]]
+id = "LOWW_TWR"
+controlled_by = ["LOWW_TWR"]
+
+[[stations]]
+id = "LOWW_TWR"
+controlled_by = ["LOWW_E_TWR"]"#,
+            good: r#"[[stations]]
+id = "LOWW_TWR"
+controlled_by = ["LOWW_TWR", "LOWW_E_TWR"]"#,
+        }],
+    },
+    Code {
+        code: UNKNOWN_POSITION,
+        name: "unknown-position",
+        title: "`controlled_by` references an unknown position",
+        description: "\
+A station's `controlled_by` list contains a position ID that is not defined in any
+`positions.toml`. Position IDs are case-sensitive. The help line suggests similarly named
+positions, if there are any.",
+        rationale: "\
+Coverage is resolved by checking which positions of the `controlled_by` list are online. An
+unknown position can never be online, so the entry is most likely a typo that leaves the station
+uncovered when only the intended position is online.",
+        examples: &[Example {
+            caption: "Reference the position by its exact ID",
+            file: "stations.toml",
+            bad: r#"[[stations]]
+id = "LOWW_TWR"
+controlled_by = ["LOWW_TWR", "LOWW_E_TWE"]"#,
+            good: r#"[[stations]]
+id = "LOWW_TWR"
+controlled_by = ["LOWW_TWR", "LOWW_E_TWR"]"#,
+        }],
+    },
+    Code {
+        code: UNKNOWN_PARENT_STATION,
+        name: "unknown-parent-station",
+        title: "`parent_id` references an unknown station",
+        description: "\
+A station's `parent_id` refers to a station that is not defined in any `stations.toml`. Station
+IDs use underscores, not spaces or dashes.",
+        rationale: "\
+A station inherits the coverage list of its parent. With an unknown parent, the inherited part of
+the list is missing and the station is uncovered whenever none of its own positions is online.",
+        examples: &[Example {
+            caption: "Reference the parent station by its exact ID",
+            file: "stations.toml",
+            bad: r#"[[stations]]
+id = "LOWW_DEL"
+parent_id = "LOWW GND"
+controlled_by = ["LOWW_DEL"]"#,
+            good: r#"[[stations]]
+id = "LOWW_DEL"
+parent_id = "LOWW_GND"
+controlled_by = ["LOWW_DEL"]"#,
+        }],
+    },
+    Code {
+        code: UNKNOWN_STATION,
+        name: "unknown-station",
+        title: "profile key references an unknown station",
+        description: "\
+A direct access key or geo button of a profile has a `station_id` that is not defined in any
+`stations.toml`.",
+        rationale: "\
+Pressing the key would call a station vacs does not know, so the call can never be routed to a
+controller.",
+        examples: &[Example {
+            caption: "Reference the station by its exact ID",
+            file: "profiles/LOWW.json",
+            bad: r#"{
+  "label": ["LOWW", "Tower"],
+  "station_id": "LOWW_TWRR"
+}"#,
+            good: r#"{
+  "label": ["LOWW", "Tower"],
+  "station_id": "LOWW_TWR"
+}"#,
+        }],
+    },
+    Code {
+        code: UNKNOWN_PROFILE,
+        name: "unknown-profile",
+        title: "`profile_id` references an unknown profile",
+        description: "\
+A position's `profile_id` refers to a profile that is not defined in any `profiles` directory.
+Profile IDs are taken from the `id` field of the profile, not from its file name.",
+        rationale: "\
+Controllers logging in on the position would not get the intended profile loaded.",
+        examples: &[Example {
+            caption: "Reference the profile by its `id`",
+            file: "positions.toml",
+            bad: r#"[[positions]]
+id = "LOWW_TWR"
+prefixes = ["LOWW"]
+frequency = "119.400"
+facility_type = "TWR"
+profile_id = "LOWW.json""#,
+            good: r#"[[positions]]
+id = "LOWW_TWR"
+prefixes = ["LOWW"]
+frequency = "119.400"
+facility_type = "TWR"
+profile_id = "LOWW""#,
+        }],
+    },
+    Code {
+        code: CIRCULAR_INHERITANCE,
+        name: "circular-inheritance",
+        title: "circular station inheritance",
+        description: "\
+Following the `parent_id` references of a station leads back to a station already in the chain,
+e.g. `A → B → C → A`. The message lists the full cycle, the location points at the `parent_id`
+closing it. Each cycle is reported once, even if several chains lead into it.",
+        rationale: "\
+Coverage resolution stops at the first station visited twice, so the coverage lists of all
+stations in or inheriting from the cycle are silently truncated. This is always a data bug and
+cannot be turned off in the lint configuration.",
+        examples: &[Example {
+            caption: "End the chain at a station without `parent_id`",
+            file: "stations.toml",
+            bad: r#"[[stations]]
+id = "LOWW_GND"
+parent_id = "LOWW_TWR"
+controlled_by = ["LOWW_GND"]
+
+[[stations]]
+id = "LOWW_TWR"
+parent_id = "LOWW_GND"
+controlled_by = ["LOWW_TWR"]"#,
+            good: r#"[[stations]]
+id = "LOWW_GND"
+parent_id = "LOWW_TWR"
+controlled_by = ["LOWW_GND"]
+
+[[stations]]
+id = "LOWW_TWR"
+controlled_by = ["LOWW_TWR"]"#,
+        }],
+    },
+    Code {
+        code: MISSING_CONTROLLED_BY,
+        name: "missing-controlled-by",
+        title: "station without `controlled_by`",
+        description: "\
+A station defines neither a `controlled_by` list nor a `parent_id` to inherit one from, or its
+`controlled_by` list is empty. Stations with a parent may omit `controlled_by`, their coverage
+is inherited.",
+        rationale: "\
+The coverage list of a station determines which online position covers it. A station without
+any list can never be covered and would never be shown as staffed.",
+        examples: &[Example {
+            caption: "List the positions covering the station, or inherit them from a parent",
+            file: "stations.toml",
+            bad: r#"[[stations]]
+id = "LOWW_TWR""#,
+            good: r#"[[stations]]
+id = "LOWW_TWR"
+controlled_by = ["LOWW_TWR", "LOWW_E_TWR"]"#,
+        }],
+    },
+    Code {
+        code: INVALID_FREQUENCY,
+        name: "invalid-frequency",
+        title: "invalid frequency format",
+        description: "\
+A position's `frequency` is not in `XXX.XXX` format, with exactly three digits before and after
+the decimal point.",
+        rationale: "\
+Logins are matched to positions by comparing frequencies as strings, in the format of the VATSIM
+data feed. A frequency in any other format never matches a login.",
+        examples: &[Example {
+            caption: "Frequencies need three decimal places",
+            file: "positions.toml",
+            bad: r#"[[positions]]
+id = "LOWW_TWR"
+prefixes = ["LOWW"]
+frequency = "119.4"
+facility_type = "TWR""#,
+            good: r#"[[positions]]
+id = "LOWW_TWR"
+prefixes = ["LOWW"]
+frequency = "119.400"
+facility_type = "TWR""#,
+        }],
+    },
+    Code {
+        code: UNKNOWN_RULE,
+        name: "unknown-rule",
+        title: "unknown rule in lint configuration",
+        description: "\
+The lint configuration configures a rule that does not exist, usually because of a typo. Rule
+names are kebab-case, run `vacs-data explain` for a list of all rules.",
+        rationale: "\
+A misspelled rule is silently not configured, e.g. its allow-list has no effect. The run fails so
+the typo is noticed.",
+        examples: &[Example {
+            caption: "Use the kebab-case rule name",
+            file: "vacs-data.toml",
+            bad: r#"[rules.ambiguous_positions]
+severity = "error""#,
+            good: r#"[rules.ambiguous-positions]
+severity = "error""#,
+        }],
+    },
+    Code {
+        code: AMBIGUOUS_POSITIONS,
+        name: "ambiguous-positions",
+        title: "ambiguous positions",
+        description: "\
+Two or more positions share frequency and facility type and have overlapping callsign prefixes.
+A controller logging in with a callsign that does not exactly match a position ID matches all of
+them at once.",
+        rationale: "\
+vacs cannot pick a position automatically for an ambiguous login and asks the controller to
+select one. If the overlap is intended, e.g. for sectors sharing a frequency, allow-list the
+group in the lint configuration instead.",
+        examples: &[Example {
+            caption: "Use prefixes specific to each position",
+            file: "positions.toml",
+            bad: r#"[[positions]]
+id = "LOWW_APP"
+prefixes = ["LOWW"]
+frequency = "134.675"
+facility_type = "APP"
+
+[[positions]]
+id = "LOWW_N_APP"
+prefixes = ["LOWW"]
+frequency = "134.675"
+facility_type = "APP""#,
+            good: r#"[[positions]]
+id = "LOWW_APP"
+prefixes = ["LOWW"]
+frequency = "134.675"
+facility_type = "APP"
+
+[[positions]]
+id = "LOWW_N_APP"
+prefixes = ["LOWW_N"]
+frequency = "134.675"
+facility_type = "APP""#,
+        }],
+    },
+    Code {
+        code: CROSS_FIR_INHERITANCE,
+        name: "cross-fir-inheritance",
+        title: "station inherits from another FIR",
+        description: "\
+A station's `parent_id` refers to a station defined in another FIR directory, so its coverage
+falls back to positions of the neighbouring FIR.",
+        rationale: "\
+Inheriting across FIRs couples two FIR directories usually maintained by different people: a
+change to the parent silently changes the coverage of the child. If the fallback is intended,
+allow-list the station and its parent in the lint configuration.",
+        examples: &[Example {
+            caption: "List the neighbouring positions explicitly",
+            file: "stations.toml",
+            bad: r#"[[stations]]
+id = "LOWW_APP"
+parent_id = "LJLA_CTR"
+controlled_by = ["LOWW_APP"]"#,
+            good: r#"[[stations]]
+id = "LOWW_APP"
+controlled_by = ["LOWW_APP", "LOVV_CTR"]"#,
+        }],
+    },
+    Code {
+        code: GEO_BUTTON_TOO_SMALL,
+        name: "geo-button-too-small",
+        title: "geo button too small for its label",
+        description: "\
+A button of a geo profile is smaller than its label once the profile is laid out. Every line of
+the label needs 20px of height and 9.6px of width per character, plus 4px of padding on each
+side, so a 3-line label needs a button of at least 68px (`4.25` rem).",
+        rationale: "\
+The client clips labels that do not fit their button, which is usually only noticed once the
+profile is in use.",
+        examples: &[Example {
+            caption: "Increase the button size",
+            file: "LOVV.json",
+            bad: r#"{ "label": ["B", "LOWS", "APP"], "size": 3.5 }"#,
+            good: r#"{ "label": ["B", "LOWS", "APP"], "size": 6.25 }"#,
+        }],
+    },
+    Code {
+        code: GEO_PERCENTAGE_SUM,
+        name: "geo-percentage-sum",
+        title: "geo container children exceed 100%",
+        description: "\
+The `width` (in a `row` container) or `height` (in a `col` container) percentages of the children
+of a geo profile container sum up to more than 100% of the container.",
+        rationale: "\
+The children cannot all get their share, so the client shrinks them or pushes the last ones out
+of the container. Gaps and fixed sizes take up further room, so the sum should usually stay
+well below 100%.",
+        examples: &[Example {
+            caption: "Split the container between its children",
+            file: "LOVV.json",
+            bad: r#"{
+  "direction": "row",
+  "children": [
+    { "direction": "col", "width": "60%", "children": [] },
+    { "direction": "col", "width": "60%", "children": [] }
+  ]
+}"#,
+            good: r#"{
+  "direction": "row",
+  "children": [
+    { "direction": "col", "width": "50%", "children": [] },
+    { "direction": "col", "width": "50%", "children": [] }
+  ]
+}"#,
+        }],
+    },
+    Code {
+        code: GEO_NODE_OVERFLOW,
+        name: "geo-node-overflow",
+        title: "geo node outside its container",
+        description: "\
+A container, button or divider of a geo profile ends up partly outside its container once the
+profile is laid out within the 900×544px page area of the client. Buttons and dividers never
+shrink, containers only down to the size of their content, so too many or too large buttons,
+gaps and paddings push nodes over the edge.",
+        rationale: "\
+Nodes outside their container are cut off or overlap their neighbours in the client. Use
+`vacs-data render-snapshots` to see the computed layout.",
+        examples: &[Example {
+            caption: "Reduce the button size to fit the row",
+            file: "LZBB.json",
+            bad: r#"{
+  "direction": "row",
+  "gap": 0.5,
+  "children": [
+    { "label": ["WEST"], "size": 10 },
+    { "label": ["CENTER"], "size": 10 },
+    { "label": ["EAST"], "size": 10 },
+    { "label": ["FIS"], "size": 10 }
+  ]
+}"#,
+            good: r#"{
+  "direction": "row",
+  "gap": 0.5,
+  "children": [
+    { "label": ["WEST"], "size": 9 },
+    { "label": ["CENTER"], "size": 9 },
+    { "label": ["EAST"], "size": 9 },
+    { "label": ["FIS"], "size": 9 }
+  ]
+}"#,
+        }],
+    },
+    Code {
+        code: INPUT_NOT_FOUND,
+        name: "input-not-found",
+        title: "input does not exist",
+        description: "\
+The input file or directory given on the command line does not exist. Relative paths are
+resolved against the current working directory.",
+        rationale: "Nothing can be validated or imported without input.",
+        examples: &[],
+    },
+    Code {
+        code: INPUT_NOT_DIRECTORY,
+        name: "input-not-directory",
+        title: "input is not a directory",
+        description: "\
+The command expects a directory, e.g. the dataset root containing one directory per FIR or a
+directory of data feed snapshots, but a file was given.",
+        rationale: "The dataset and snapshot collections span multiple files.",
+        examples: &[],
+    },
+    Code {
+        code: UNKNOWN_FIR,
+        name: "unknown-fir",
+        title: "FIR does not exist in dataset",
+        description: "\
+The FIR given with `--fir` has no directory in the dataset. FIRs are identified by the name of
+their directory, e.g. `LO` for `dataset/LO`.",
+        rationale: "Restricting a command to an unknown FIR would silently produce no results.",
+        examples: &[],
+    },
+    Code {
+        code: UNREADABLE_FILE,
+        name: "unreadable-file",
+        title: "file could not be read",
+        description: "\
+A file or directory could not be read, e.g. because of missing permissions. The message contains
+the error reported by the operating system.",
+        rationale: "Skipping unreadable files would silently validate an incomplete dataset.",
+        examples: &[],
+    },
+    Code {
+        code: INVALID_FILE,
+        name: "invalid-file",
+        title: "file could not be parsed",
+        description: "\
+A TOML or JSON file is not well-formed or does not have the expected structure. The message
+contains the line and column reported by the parser.",
+        rationale: "\
+Entries of a file that cannot be parsed are unknown, so all references to them would be
+reported as missing.",
+        examples: &[Example {
+            caption: "Strings must be quoted",
+            file: "stations.toml",
+            bad: r#"[[stations]]
+id = LOWW_TWR
+controlled_by = ["LOWW_TWR"]"#,
+            good: r#"[[stations]]
+id = "LOWW_TWR"
+controlled_by = ["LOWW_TWR"]"#,
+        }],
+    },
+    Code {
+        code: INVALID_LOGIN,
+        name: "invalid-login",
+        title: "invalid login",
+        description: "\
+A login given with `--login` or `--logins-file` is not in `CALLSIGN:FREQUENCY[:FACILITY]`
+format, or its facility type cannot be derived from the callsign suffix.",
+        rationale: "Logins are matched by callsign, frequency and facility type, all are required.",
+        examples: &[Example {
+            caption: "Separate the fields with colons",
+            file: "logins.txt",
+            bad: "LOWW_TWR 119.400",
+            good: "LOWW_TWR:119.400:TWR",
+        }],
+    },
+    Code {
+        code: OUTPUT_EXISTS,
+        name: "output-exists",
+        title: "output file already exists",
+        description: "\
+An importer would overwrite an existing output file. Pass `--merge` to add new entries to the
+existing file or `--overwrite` to replace it.",
+        rationale: "Output files are usually edited by hand after importing, so they are never replaced implicitly.",
+        examples: &[],
+    },
+    Code {
+        code: WRITE_FAILED,
+        name: "write-failed",
+        title: "output could not be written",
+        description: "\
+An output file or directory could not be created or written, or the output could not be
+serialized. The message contains the underlying error.",
+        rationale: "A partially written output would be mistaken for a complete one.",
+        examples: &[],
+    },
+    Code {
+        code: CONVERSION_FAILED,
+        name: "conversion-failed",
+        title: "input could not be converted",
+        description: "\
+The import source was parsed, but its content cannot be converted to stations or positions,
+e.g. because required data is missing.",
+        rationale: "Entries that cannot be converted faithfully are not imported at all.",
+        examples: &[],
+    },
+    Code {
+        code: UNKNOWN_STATION_ID,
+        name: "unknown-station-id",
+        title: "station does not exist in dataset",
+        description: "\
+The station given on the command line is not defined in any `stations.toml` of the dataset.
+Station IDs are matched ignoring case.",
+        rationale: "Only stations of the dataset can be explained.",
+        examples: &[],
+    },
+    Code {
+        code: UNKNOWN_PROFILE_ID,
+        name: "unknown-profile-id",
+        title: "profile does not exist in dataset",
+        description: "\
+The profile given on the command line is neither an existing profile file nor the `id` of a
+profile in any `profiles` directory of the dataset. Profile IDs are matched ignoring case.",
+        rationale: "Only existing profiles can be rendered.",
+        examples: &[],
+    },
+];
+
+/// Looks up a code by its code or name, ignoring case.
+#[must_use]
+pub fn lookup(code: &str) -> Option<&'static Code> {
+    CODES
+        .iter()
+        .find(|c| c.code.eq_ignore_ascii_case(code) || c.name.eq_ignore_ascii_case(code))
+}
+
+/// Renders the explanation of a code as Markdown.
+#[must_use]
+pub fn explain(code: &Code) -> String {
+    let mut out = format!("# {}: {}\n\n", code.code, code.title);
+    let _ = writeln!(out, "{}\n", code.description);
+    let _ = writeln!(out, "## Rationale\n\n{}\n", code.rationale);
+
+    for example in code.examples {
+        let lang = example.file.rsplit('.').next().unwrap_or_default();
+        let lang = if lang == "txt" { "" } else { lang };
+        let _ = writeln!(out, "## Example: {}\n", example.caption);
+        let _ = writeln!(
+            out,
+            "Erroneous `{}`:\n\n```{lang}\n{}\n```\n",
+            example.file, example.bad
+        );
+        let _ = writeln!(out, "Fixed:\n\n```{lang}\n{}\n```\n", example.good);
+    }
+
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Renders a list of all codes as a Markdown table.
+#[must_use]
+pub fn list() -> String {
+    let mut out = String::from("| Code | Name | Description |\n| :-- | :-- | :-- |\n");
+    for code in CODES {
+        let _ = writeln!(out, "| {} | {} | {} |", code.code, code.name, code.title);
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
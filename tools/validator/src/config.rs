@@ -0,0 +1,143 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use vacs_data_diagnostics::{Severity, log};
+
+/// Name of the lint configuration file, looked up in the dataset root and its parent directory.
+pub const CONFIG_FILE_NAME: &str = "vacs-data.toml";
+
+/// Lint configuration read from `vacs-data.toml`.
+///
+/// ```toml
+/// [rules.ambiguous-positions]
+/// severity = "warning"
+/// allow = [["LOWI_E_APP", "LOWI_S_APP"]]
+///
+/// [rules.ambiguous-positions.firs]
+/// ES = "off"
+/// LO = "error"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConfig {
+    /// Severity override for the whole dataset.
+    pub severity: Option<Level>,
+    /// Severity overrides per FIR directory, taking precedence over `severity`.
+    #[serde(default)]
+    pub firs: BTreeMap<String, Level>,
+    /// Entities findings are suppressed for. A group of IDs only suppresses findings involving
+    /// no other entities than the ones listed.
+    #[serde(default)]
+    pub allow: Vec<AllowEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Level {
+    Off,
+    Severity(Severity),
+}
+
+impl TryFrom<String> for Level {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "off" | "allow" => Ok(Level::Off),
+            _ => value.parse().map(Level::Severity).map_err(|_| {
+                format!("invalid level '{value}'. expected one of: off, info, warning, error")
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum AllowEntry {
+    Entity(String),
+    Group(Vec<String>),
+}
+
+impl AllowEntry {
+    /// Returns whether all of the given entities are covered by this entry.
+    #[must_use]
+    pub fn allows(&self, entities: &[String]) -> bool {
+        let ids = match self {
+            AllowEntry::Entity(id) => std::slice::from_ref(id),
+            AllowEntry::Group(ids) => ids.as_slice(),
+        };
+        !entities.is_empty()
+            && entities
+                .iter()
+                .all(|e| ids.iter().any(|id| id.eq_ignore_ascii_case(e)))
+    }
+}
+
+impl Config {
+    /// Looks for a configuration file in the dataset root, then in its parent directory
+    /// (usually the repository root). Returns the default configuration if none exists.
+    pub fn discover(input: &Path) -> crate::Result<Self> {
+        let candidates = [
+            Some(input.join(CONFIG_FILE_NAME)),
+            input
+                .canonicalize()
+                .ok()
+                .and_then(|p| p.parent().map(|p| p.join(CONFIG_FILE_NAME))),
+        ];
+
+        match candidates.into_iter().flatten().find(|p| p.is_file()) {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        log::info(format_args!("Reading lint configuration from {path:?}"));
+
+        let content = crate::read_to_string(path)?;
+        let mut config: Config =
+            toml::from_str(&content).map_err(|source| crate::Error::ParseToml {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        config.path = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// Returns the configured level of a rule for a finding in the given FIR, if overridden.
+    #[must_use]
+    pub fn level(&self, rule: &str, fir: Option<&str>) -> Option<Level> {
+        let rule = self.rules.get(rule)?;
+        fir.and_then(|fir| {
+            rule.firs
+                .iter()
+                .find(|(id, _)| id.eq_ignore_ascii_case(fir))
+                .map(|(_, level)| *level)
+        })
+        .or(rule.severity)
+    }
+
+    #[must_use]
+    pub fn is_allowed(&self, rule: &str, entities: &[String]) -> bool {
+        self.rules
+            .get(rule)
+            .is_some_and(|r| r.allow.iter().any(|a| a.allows(entities)))
+    }
+
+    /// Adds an allow-list entry for a rule, e.g. from a command line flag.
+    pub fn allow(&mut self, rule: &str, entry: AllowEntry) {
+        self.rules
+            .entry(rule.to_string())
+            .or_default()
+            .allow
+            .push(entry);
+    }
+}
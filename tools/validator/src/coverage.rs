@@ -0,0 +1,291 @@
+use crate::dataset::Dataset;
+use console::style;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use vacs_data_diagnostics::log;
+use vacs_protocol::vatsim::{PositionId, StationId};
+use vacs_vatsim::FacilityType;
+use vacs_vatsim::coverage::network::Network;
+use vacs_vatsim::coverage::position::PositionRaw;
+use vacs_vatsim::coverage::station::StationRaw;
+
+/// A controller login on the VATSIM network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Login {
+    pub callsign: String,
+    pub frequency: String,
+    pub facility_type: FacilityType,
+}
+
+impl Login {
+    #[must_use]
+    pub fn new(callsign: &str, frequency: &str, facility_type: FacilityType) -> Self {
+        Self {
+            callsign: normalize_callsign(callsign),
+            frequency: frequency.to_string(),
+            facility_type,
+        }
+    }
+}
+
+impl FromStr for Login {
+    type Err = String;
+
+    /// Parses a login in `CALLSIGN:FREQUENCY[:FACILITY]` format. If the facility type is
+    /// omitted, it is derived from the callsign suffix (e.g. `_CTR`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.trim().split(':').map(str::trim).collect();
+        let (callsign, frequency, facility) = match parts.as_slice() {
+            [callsign, frequency] => (*callsign, *frequency, None),
+            [callsign, frequency, facility] => (*callsign, *frequency, Some(*facility)),
+            _ => {
+                return Err(format!(
+                    "invalid login '{s}'. expected CALLSIGN:FREQUENCY[:FACILITY]"
+                ));
+            }
+        };
+
+        let facility = facility
+            .or_else(|| callsign.rsplit('_').next())
+            .unwrap_or_default();
+        let Ok(facility_type) = facility.parse() else {
+            return Err(format!("invalid facility type '{facility}' in login '{s}'"));
+        };
+
+        Ok(Self::new(callsign, frequency, facility_type))
+    }
+}
+
+/// Reads logins from a file containing one `CALLSIGN:FREQUENCY[:FACILITY]` entry per line.
+/// Empty lines and lines starting with `#` are ignored.
+pub fn read_logins(path: &Path) -> crate::Result<Vec<Login>> {
+    tracing::debug!("Reading logins from {path:?}");
+    let content = std::fs::read_to_string(path).map_err(|source| crate::Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let mut logins = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let login = line
+            .parse::<Login>()
+            .map_err(|message| crate::Error::InvalidLogin {
+                path: path.to_path_buf(),
+                line: idx + 1,
+                message,
+            })?;
+        logins.push(login);
+    }
+    Ok(logins)
+}
+
+/// Normalizes a callsign the same way vacs does before matching it against positions.
+#[must_use]
+pub fn normalize_callsign(callsign: &str) -> String {
+    callsign.replace("__", "_").to_ascii_uppercase()
+}
+
+#[derive(Debug, Clone)]
+pub enum PositionMatch<'a> {
+    Exact(&'a PositionRaw),
+    Prefix(&'a PositionRaw),
+    Ambiguous(Vec<&'a PositionRaw>),
+    None,
+}
+
+impl<'a> PositionMatch<'a> {
+    #[must_use]
+    pub fn position(&self) -> Option<&'a PositionRaw> {
+        match self {
+            PositionMatch::Exact(p) | PositionMatch::Prefix(p) => Some(p),
+            _ => None,
+        }
+    }
+}
+
+/// Maps a login to a position using exact ID matching first, falling back to prefix matching.
+pub fn match_position<'a>(
+    positions: impl IntoIterator<Item = &'a PositionRaw>,
+    login: &Login,
+) -> PositionMatch<'a> {
+    let mut candidates = Vec::new();
+
+    for position in positions {
+        if position.frequency != login.frequency || position.facility_type != login.facility_type {
+            continue;
+        }
+        if position
+            .id
+            .to_string()
+            .eq_ignore_ascii_case(&login.callsign)
+        {
+            return PositionMatch::Exact(position);
+        }
+        if position
+            .prefixes
+            .iter()
+            .any(|p| login.callsign.starts_with(&p.to_ascii_uppercase()))
+        {
+            candidates.push(position);
+        }
+    }
+
+    match candidates.len() {
+        0 => PositionMatch::None,
+        1 => PositionMatch::Prefix(candidates[0]),
+        _ => {
+            candidates.sort_by(|a, b| a.id.cmp(&b.id));
+            PositionMatch::Ambiguous(candidates)
+        }
+    }
+}
+
+/// Returns the station followed by all of its ancestors, stopping at missing parents or at the
+/// first station already visited in the chain.
+pub fn inheritance_chain<'a>(
+    stations: &HashMap<&StationId, &'a StationRaw>,
+    id: &StationId,
+) -> Vec<&'a StationRaw> {
+    crate::inheritance::Chain::resolve(stations, id).stations
+}
+
+/// Resolves the effective coverage list of a station: its own `controlled_by` list followed by
+/// the lists of all ancestors, deduplicated in a stable manner.
+#[must_use]
+pub fn resolve_coverage(
+    stations: &HashMap<&StationId, &StationRaw>,
+    id: &StationId,
+) -> Vec<PositionId> {
+    let mut seen = HashSet::new();
+    inheritance_chain(stations, id)
+        .into_iter()
+        .flat_map(|s| s.controlled_by.iter())
+        .filter(|p| seen.insert(*p))
+        .cloned()
+        .collect()
+}
+
+/// Outcome of matching a set of logins against the dataset and resolving station coverage.
+#[derive(Debug, Default)]
+pub struct Coverage<'a> {
+    pub matches: Vec<(&'a Login, PositionMatch<'a>)>,
+    /// Covering position for every station, `None` if the station is not covered.
+    pub stations: HashMap<&'a StationId, Option<PositionId>>,
+}
+
+impl<'a> Coverage<'a> {
+    pub fn compute(dataset: &'a Dataset, logins: &'a [Login]) -> Self {
+        let matches: Vec<_> = logins
+            .iter()
+            .map(|login| (login, match_position(dataset.positions(), login)))
+            .collect();
+
+        let online: HashSet<&PositionId> = matches
+            .iter()
+            .filter_map(|(_, m)| m.position())
+            .map(|p| &p.id)
+            .collect();
+
+        let station_map = dataset.station_map();
+        let stations = dataset
+            .stations()
+            .map(|s| {
+                let covering = resolve_coverage(&station_map, &s.id)
+                    .into_iter()
+                    .find(|p| online.contains(p));
+                (&s.id, covering)
+            })
+            .collect();
+
+        Self { matches, stations }
+    }
+}
+
+pub fn simulate(input: &PathBuf, logins: &[Login], fir: Option<&str>) -> crate::Result<()> {
+    log::info(format_args!("Simulating coverage for dataset: {input:?}"));
+
+    crate::check_input_dir(input)?;
+
+    if let Err(errors) = Network::load_from_dir(input) {
+        let diagnostics = crate::report_errors(input, &errors);
+        return Err(crate::Error::Dataset(diagnostics.len()));
+    }
+
+    let dataset = Dataset::load(input)?;
+    if let Some(fir) = fir
+        && dataset.fir(fir).is_none()
+    {
+        return Err(crate::Error::UnknownFir {
+            fir: fir.to_string(),
+            input: input.clone(),
+        });
+    }
+
+    let coverage = Coverage::compute(&dataset, logins);
+
+    let (mut matched, mut ambiguous, mut unmatched) = (0, 0, 0);
+    for (login, m) in &coverage.matches {
+        if let Some(fir) = fir.and_then(|fir| dataset.fir(fir))
+            && !fir.is_relevant_callsign(&login.callsign)
+        {
+            continue;
+        }
+
+        let callsign = style(format!("`{}`", login.callsign)).cyan();
+        match m {
+            PositionMatch::Exact(p) | PositionMatch::Prefix(p) => {
+                matched += 1;
+                println!(
+                    "Login {callsign} ({} {}) matched position {}",
+                    login.frequency,
+                    login.facility_type.as_str(),
+                    style(format!("`{}`", p.id)).cyan()
+                );
+            }
+            PositionMatch::Ambiguous(candidates) => {
+                ambiguous += 1;
+                log::warn(format_args!(
+                    "Login {callsign} ({} {}) matched multiple positions: {}",
+                    login.frequency,
+                    login.facility_type.as_str(),
+                    candidates
+                        .iter()
+                        .map(|p| p.id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            PositionMatch::None => {
+                unmatched += 1;
+                log::warn(format_args!(
+                    "Login {callsign} ({} {}) did not match any position",
+                    login.frequency,
+                    login.facility_type.as_str(),
+                ));
+            }
+        }
+    }
+    println!("Matched {matched} logins, {ambiguous} ambiguous, {unmatched} unmatched");
+
+    for f in &dataset.firs {
+        if fir.is_some_and(|fir| !f.id.eq_ignore_ascii_case(fir)) || f.stations.is_empty() {
+            continue;
+        }
+
+        println!("{}", style(&f.id).bold());
+        for station in &f.stations {
+            let covering = match coverage.stations.get(&station.id) {
+                Some(Some(position)) => style(position.to_string()).cyan().to_string(),
+                _ => style("not covered").dim().to_string(),
+            };
+            println!("  {} -> {covering}", station.id);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::coverage::Login;
+use serde::Deserialize;
+use std::path::Path;
+use vacs_vatsim::FacilityType;
+
+/// Subset of the VATSIM data feed (`vatsim-data.json`) relevant for coverage calculations.
+#[derive(Debug, Deserialize)]
+struct DataFeed {
+    controllers: Vec<DataFeedController>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DataFeedController {
+    callsign: String,
+    frequency: String,
+    facility: u8,
+}
+
+impl DataFeedController {
+    /// Facility type of the controller, derived from the callsign suffix if possible and
+    /// falling back to the numeric facility reported by the data feed.
+    fn facility_type(&self) -> FacilityType {
+        if let Some(Ok(facility_type)) = self.callsign.rsplit('_').next().map(str::parse) {
+            return facility_type;
+        }
+
+        let facility = match self.facility {
+            1 => "FSS",
+            2 => "DEL",
+            3 => "GND",
+            4 => "TWR",
+            5 => "APP",
+            6 => "CTR",
+            _ => return FacilityType::Unknown,
+        };
+        facility.parse().unwrap_or(FacilityType::Unknown)
+    }
+}
+
+/// Reads all controller logins from a saved VATSIM data feed snapshot, skipping observers and
+/// logins with an unknown facility type.
+pub fn read_snapshot(path: &Path) -> crate::Result<Vec<Login>> {
+    tracing::debug!("Reading snapshot {path:?}");
+    let file = std::fs::File::open(path).map_err(|source| crate::Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let feed: DataFeed =
+        serde_json::from_reader(std::io::BufReader::new(file)).map_err(|source| {
+            crate::Error::ParseJson {
+                path: path.to_path_buf(),
+                source,
+            }
+        })?;
+
+    Ok(feed
+        .controllers
+        .iter()
+        .filter_map(|c| {
+            if c.facility == 0 {
+                tracing::trace!("Skipping observer `{}`", c.callsign);
+                return None;
+            }
+            let facility_type = c.facility_type();
+            if facility_type == FacilityType::Unknown {
+                tracing::trace!("Skipping `{}` with unknown facility type", c.callsign);
+                return None;
+            }
+            Some(Login::new(&c.callsign, &c.frequency, facility_type))
+        })
+        .collect())
+}
@@ -0,0 +1,143 @@
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use vacs_protocol::vatsim::{PositionId, StationId};
+use vacs_vatsim::coverage::position::{PositionConfigFile, PositionRaw};
+use vacs_vatsim::coverage::station::{StationConfigFile, StationRaw};
+
+/// Raw station and position definitions of a whole dataset, grouped by FIR directory.
+///
+/// [`Network`](vacs_vatsim::coverage::network::Network) only exposes the validated network, so
+/// analyses that need the configured order of entries read the raw files again.
+#[derive(Debug, Default)]
+pub struct Dataset {
+    pub firs: Vec<Fir>,
+}
+
+#[derive(Debug)]
+pub struct Fir {
+    pub id: String,
+    pub path: PathBuf,
+    pub stations: Vec<StationRaw>,
+    pub positions: Vec<PositionRaw>,
+}
+
+impl Dataset {
+    pub fn load(input: &Path) -> crate::Result<Self> {
+        let mut dirs = std::fs::read_dir(input)
+            .map_err(|source| crate::Error::Read {
+                path: input.to_path_buf(),
+                source,
+            })?
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect::<Vec<_>>();
+        dirs.sort();
+
+        let mut firs = Vec::with_capacity(dirs.len());
+        for path in dirs {
+            let Some(id) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+                continue;
+            };
+
+            let stations = read_config::<StationConfigFile>(&path, "stations")?
+                .map(|c| c.stations)
+                .unwrap_or_default();
+            let positions = read_config::<PositionConfigFile>(&path, "positions")?
+                .map(|c| c.positions)
+                .unwrap_or_default();
+
+            firs.push(Fir {
+                id,
+                path,
+                stations,
+                positions,
+            });
+        }
+
+        Ok(Self { firs })
+    }
+
+    pub fn fir(&self, id: &str) -> Option<&Fir> {
+        self.firs.iter().find(|f| f.id.eq_ignore_ascii_case(id))
+    }
+
+    pub fn stations(&self) -> impl Iterator<Item = &StationRaw> {
+        self.firs.iter().flat_map(|f| f.stations.iter())
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &PositionRaw> {
+        self.firs.iter().flat_map(|f| f.positions.iter())
+    }
+
+    #[must_use]
+    pub fn fir_of_position(&self, id: &PositionId) -> Option<&Fir> {
+        self.firs
+            .iter()
+            .find(|f| f.positions.iter().any(|p| &p.id == id))
+    }
+
+    #[must_use]
+    pub fn fir_of_station(&self, id: &StationId) -> Option<&Fir> {
+        self.firs
+            .iter()
+            .find(|f| f.stations.iter().any(|s| &s.id == id))
+    }
+
+    #[must_use]
+    pub fn station_map(&self) -> HashMap<&StationId, &StationRaw> {
+        self.stations().map(|s| (&s.id, s)).collect()
+    }
+
+    #[must_use]
+    pub fn position_map(&self) -> HashMap<&PositionId, &PositionRaw> {
+        self.positions().map(|p| (&p.id, p)).collect()
+    }
+}
+
+impl Fir {
+    /// Two-letter country code all IDs of the FIR start with, e.g. `ED` for `EDMM`.
+    #[must_use]
+    pub fn country_code(&self) -> &str {
+        self.id.get(..2).unwrap_or(&self.id)
+    }
+
+    /// Returns whether a callsign starts with any prefix used by the FIR's positions.
+    #[must_use]
+    pub fn is_relevant_callsign(&self, callsign: &str) -> bool {
+        self.positions
+            .iter()
+            .flat_map(|p| p.prefixes.iter())
+            .any(|prefix| callsign.starts_with(&prefix.to_ascii_uppercase()))
+    }
+}
+
+/// Reads `{name}.toml` or `{name}.json` from a FIR directory, preferring TOML if both exist.
+fn read_config<T: DeserializeOwned>(dir: &Path, name: &str) -> crate::Result<Option<T>> {
+    let toml_path = dir.join(format!("{name}.toml"));
+    if toml_path.is_file() {
+        tracing::debug!("Reading {toml_path:?}");
+        let content = crate::read_to_string(&toml_path)?;
+        return toml::from_str(&content)
+            .map(Some)
+            .map_err(|source| crate::Error::ParseToml {
+                path: toml_path,
+                source,
+            });
+    }
+
+    let json_path = dir.join(format!("{name}.json"));
+    if json_path.is_file() {
+        tracing::debug!("Reading {json_path:?}");
+        let content = crate::read_to_string(&json_path)?;
+        return serde_json::from_str(&content).map(Some).map_err(|source| {
+            crate::Error::ParseJson {
+                path: json_path,
+                source,
+            }
+        });
+    }
+
+    Ok(None)
+}
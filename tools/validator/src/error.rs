@@ -0,0 +1,89 @@
+use crate::codes;
+use std::path::PathBuf;
+
+/// Errors returned by the validator. None of them are logged by the validator itself, so
+/// callers decide how to report them.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("input {0:?} does not exist")]
+    InputNotFound(PathBuf),
+
+    #[error("input {0:?} is not a directory")]
+    InputNotDirectory(PathBuf),
+
+    #[error("FIR {fir} does not exist in dataset {input:?}")]
+    UnknownFir { fir: String, input: PathBuf },
+
+    #[error("failed to read {path:?}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse {path:?}: {source}")]
+    ParseToml {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    #[error("failed to parse {path:?}: {source}")]
+    ParseJson {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("station {station} does not exist in dataset {input:?}")]
+    UnknownStation { station: String, input: PathBuf },
+
+    #[error("profile {profile} does not exist in dataset {input:?}")]
+    UnknownProfile { profile: String, input: PathBuf },
+
+    #[error("{0}")]
+    InvalidLoginArgument(String),
+
+    #[error("{path:?}:{line}: {message}")]
+    InvalidLogin {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+
+    #[error("failed to write {path:?}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The dataset could not be loaded. The individual errors have already been reported as
+    /// diagnostics.
+    #[error("dataset validation failed with {0} errors")]
+    Dataset(usize),
+}
+
+impl Error {
+    /// Stable code explained by `vacs-data explain`. Dataset errors have been reported with
+    /// their own codes.
+    #[must_use]
+    pub const fn code(&self) -> Option<&'static str> {
+        match self {
+            Error::InputNotFound(_) => Some(codes::INPUT_NOT_FOUND),
+            Error::InputNotDirectory(_) => Some(codes::INPUT_NOT_DIRECTORY),
+            Error::UnknownFir { .. } => Some(codes::UNKNOWN_FIR),
+            Error::UnknownStation { .. } => Some(codes::UNKNOWN_STATION_ID),
+            Error::UnknownProfile { .. } => Some(codes::UNKNOWN_PROFILE_ID),
+            Error::Read { .. } => Some(codes::UNREADABLE_FILE),
+            Error::ParseToml { .. } | Error::ParseJson { .. } => Some(codes::INVALID_FILE),
+            Error::InvalidLogin { .. } | Error::InvalidLoginArgument(_) => {
+                Some(codes::INVALID_LOGIN)
+            }
+            Error::Write { .. } => Some(codes::WRITE_FAILED),
+            Error::Dataset(_) => None,
+        }
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -0,0 +1,331 @@
+//! Step-by-step explanations of how vacs resolves single entities of the dataset.
+
+use crate::coverage::{Login, PositionMatch, match_position};
+use crate::dataset::Dataset;
+use crate::inheritance::{Chain, ChainEnd};
+use console::style;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use vacs_protocol::vatsim::{PositionId, StationId};
+use vacs_vatsim::coverage::position::PositionRaw;
+
+fn id(id: impl std::fmt::Display) -> String {
+    style(format!("`{id}`")).cyan().to_string()
+}
+
+/// Facility type and frequency of a position, padded for tabular output.
+fn position_details(
+    positions: &HashMap<&PositionId, &PositionRaw>,
+    position: &PositionId,
+    width: usize,
+) -> String {
+    // IDs don't necessarily honor the width, so pad the formatted string.
+    let name = format!("{:width$}", position.to_string());
+    match positions.get(position) {
+        Some(p) => format!("{name}  {} {}", p.facility_type.as_str(), p.frequency),
+        None => format!("{name}  {}", style("unknown position").red()),
+    }
+}
+
+/// An entry of the concatenated coverage list of a station.
+struct Entry<'a> {
+    position: &'a PositionId,
+    /// Station whose `controlled_by` list contains the entry.
+    source: &'a StationId,
+    /// Index of the first occurrence if this entry is dropped as a duplicate.
+    duplicate_of: Option<usize>,
+}
+
+/// Prints the effective coverage list of a station to stdout: its own `controlled_by` list, the
+/// lists inherited from each parent, their concatenation and the stable deduplication.
+pub fn station(input: &Path, station: &str) -> crate::Result<()> {
+    crate::check_input_dir(input)?;
+    let dataset = Dataset::load(input)?;
+
+    let stations = dataset.station_map();
+    let positions = dataset.position_map();
+    let Some(target) = dataset
+        .stations()
+        .find(|s| s.id.to_string().eq_ignore_ascii_case(station))
+    else {
+        return Err(crate::Error::UnknownStation {
+            station: station.to_string(),
+            input: input.to_path_buf(),
+        });
+    };
+
+    let chain = Chain::resolve(&stations, &target.id);
+    let fir = dataset
+        .fir_of_station(&target.id)
+        .map(|f| f.id.as_str())
+        .unwrap_or_default();
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Station {} ({fir}): {}",
+        id(&target.id),
+        chain
+            .stations
+            .iter()
+            .map(|s| id(&s.id))
+            .collect::<Vec<_>>()
+            .join(" → ")
+    );
+    match chain.end {
+        ChainEnd::Root => {}
+        ChainEnd::MissingParent(parent) => {
+            let _ = writeln!(
+                out,
+                "{}",
+                style(format!(
+                    "Chain ends early, parent {} does not exist",
+                    id(parent)
+                ))
+                .yellow()
+            );
+        }
+        ChainEnd::Cycle(parent) => {
+            let _ = writeln!(
+                out,
+                "{}",
+                style(format!(
+                    "Chain ends early, parent {} is already part of the chain (circular inheritance)",
+                    id(parent)
+                ))
+                .yellow()
+            );
+        }
+    }
+
+    let width = chain
+        .stations
+        .iter()
+        .flat_map(|s| s.controlled_by.iter())
+        .map(|p| p.to_string().len())
+        .max()
+        .unwrap_or_default();
+
+    for (i, s) in chain.stations.iter().enumerate() {
+        let title = if i == 0 {
+            "Own controlled_by".to_string()
+        } else {
+            format!("Inherited from {}", id(&s.id))
+        };
+        if s.controlled_by.is_empty() {
+            let _ = writeln!(out, "{title}: {}", style("(none)").dim());
+            continue;
+        }
+        let _ = writeln!(out, "{title}:");
+        for position in &s.controlled_by {
+            let _ = writeln!(out, "    {}", position_details(&positions, position, width));
+        }
+    }
+
+    let mut first = HashMap::new();
+    let entries = chain
+        .stations
+        .iter()
+        .flat_map(|s| s.controlled_by.iter().map(|p| (p, &s.id)))
+        .enumerate()
+        .map(|(i, (position, source))| Entry {
+            position,
+            source,
+            duplicate_of: match first.get(position) {
+                Some(&index) => Some(index),
+                None => {
+                    first.insert(position, i);
+                    None
+                }
+            },
+        })
+        .collect::<Vec<_>>();
+
+    let number_width = entries.len().to_string().len();
+    let _ = writeln!(out, "Concatenated list ({} entries):", entries.len());
+    for (i, entry) in entries.iter().enumerate() {
+        let line = format!(
+            "{:>number_width$}. {}  from {}",
+            i + 1,
+            position_details(&positions, entry.position, width),
+            id(entry.source)
+        );
+        let _ = match entry.duplicate_of {
+            Some(index) => writeln!(
+                out,
+                "    {line}  {}",
+                style(format!("dropped, duplicate of #{}", index + 1)).yellow()
+            ),
+            None => writeln!(out, "    {line}"),
+        };
+    }
+
+    let effective = entries
+        .iter()
+        .filter(|e| e.duplicate_of.is_none())
+        .collect::<Vec<_>>();
+    let _ = writeln!(
+        out,
+        "Effective coverage order ({} positions, {} duplicates dropped):",
+        effective.len(),
+        entries.len() - effective.len()
+    );
+    for (i, entry) in effective.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "    {:>number_width$}. {}",
+            i + 1,
+            position_details(&positions, entry.position, width)
+        );
+    }
+
+    print!("{out}");
+    Ok(())
+}
+
+/// Why a position was rejected for a login, empty if it was accepted.
+fn mismatches(position: &PositionRaw, login: &Login) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if position.frequency != login.frequency {
+        reasons.push(format!(
+            "frequency {} does not match {}",
+            position.frequency, login.frequency
+        ));
+    }
+    if position.facility_type != login.facility_type {
+        reasons.push(format!(
+            "facility type {} does not match {}",
+            position.facility_type.as_str(),
+            login.facility_type.as_str()
+        ));
+    }
+    reasons
+}
+
+/// Prints how a login is matched to a position to stdout: the normalized callsign, the exact ID
+/// match, every position with a matching prefix and why it was accepted or rejected, and the
+/// result.
+pub fn login(input: &Path, login: &Login, callsign: &str) -> crate::Result<()> {
+    crate::check_input_dir(input)?;
+    let dataset = Dataset::load(input)?;
+
+    let mut out = String::new();
+    if callsign == login.callsign {
+        let _ = writeln!(out, "Callsign {}", id(&login.callsign));
+    } else {
+        let _ = writeln!(
+            out,
+            "Callsign {} normalized to {} (double underscores collapsed, uppercase)",
+            id(callsign),
+            id(&login.callsign)
+        );
+    }
+    let _ = writeln!(
+        out,
+        "Frequency {}, facility type {}",
+        login.frequency,
+        login.facility_type.as_str()
+    );
+
+    let exact = dataset
+        .positions()
+        .find(|p| p.id.to_string().eq_ignore_ascii_case(&login.callsign));
+    match exact {
+        Some(position) => {
+            let reasons = mismatches(position, login);
+            let _ = if reasons.is_empty() {
+                writeln!(
+                    out,
+                    "Exact match: position {} ({} {})",
+                    id(&position.id),
+                    position.facility_type.as_str(),
+                    position.frequency
+                )
+            } else {
+                writeln!(
+                    out,
+                    "Exact match: position {} rejected, {}",
+                    id(&position.id),
+                    reasons.join(", ")
+                )
+            };
+        }
+        None => {
+            let _ = writeln!(
+                out,
+                "Exact match: no position with ID {}",
+                id(&login.callsign)
+            );
+        }
+    }
+
+    let mut candidates = dataset
+        .positions()
+        .filter_map(|p| {
+            let prefix = p
+                .prefixes
+                .iter()
+                .filter(|prefix| login.callsign.starts_with(&prefix.to_ascii_uppercase()))
+                .max_by_key(|prefix| prefix.len())?;
+            Some((p, prefix))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by(|(a, _), (b, _)| a.id.cmp(&b.id));
+
+    let _ = if candidates.is_empty() {
+        writeln!(
+            out,
+            "Prefix match: no position has a prefix matching the callsign"
+        )
+    } else {
+        writeln!(
+            out,
+            "Prefix match: {} positions with a matching prefix",
+            candidates.len()
+        )
+    };
+    for (position, prefix) in &candidates {
+        let reasons = mismatches(position, login);
+        let verdict = if reasons.is_empty() {
+            style("accepted".to_string()).green()
+        } else {
+            style(format!("rejected, {}", reasons.join(", "))).dim()
+        };
+        let _ = writeln!(
+            out,
+            "    {} (prefix {prefix}, {} {}): {verdict}",
+            id(&position.id),
+            position.facility_type.as_str(),
+            position.frequency,
+        );
+    }
+
+    let result = match match_position(dataset.positions(), login) {
+        PositionMatch::Exact(position) => style(format!(
+            "unique position {} (exact match)",
+            id(&position.id)
+        ))
+        .green(),
+        PositionMatch::Prefix(position) => style(format!(
+            "unique position {} (prefix match)",
+            id(&position.id)
+        ))
+        .green(),
+        PositionMatch::Ambiguous(positions) => style(format!(
+            "ambiguous, the controller has to select one of {}",
+            positions
+                .iter()
+                .map(|p| id(&p.id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+        .yellow(),
+        PositionMatch::None => {
+            style("no match, vacs does not recognize the login".to_string()).yellow()
+        }
+    };
+    let _ = writeln!(out, "Result: {result}");
+
+    print!("{out}");
+    Ok(())
+}
@@ -0,0 +1,286 @@
+//! Station inheritance graph of a FIR or the whole dataset, exported for Graphviz or Mermaid.
+
+use crate::dataset::Dataset;
+use crate::profile;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphFormat {
+    #[default]
+    Dot,
+    Mermaid,
+}
+
+impl GraphFormat {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::Mermaid => "mermaid",
+        }
+    }
+
+    #[must_use]
+    pub const fn variants() -> &'static [&'static str] {
+        &["dot", "mermaid"]
+    }
+}
+
+impl fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(GraphFormat::Dot),
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            other => Err(format!(
+                "invalid graph format '{other}'. expected one of: {}",
+                Self::variants().join(", ")
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NodeKind {
+    Station,
+    Position,
+    Profile,
+}
+
+#[derive(Debug)]
+struct Node {
+    kind: NodeKind,
+    id: String,
+    /// FIR directory defining the node, `None` for references to undefined IDs.
+    fir: Option<String>,
+}
+
+#[derive(Debug)]
+enum EdgeKind {
+    Parent,
+    /// 1-based rank of the position in the station's own `controlled_by` list.
+    ControlledBy(usize),
+    Profile,
+}
+
+#[derive(Debug)]
+struct Edge {
+    from: usize,
+    to: usize,
+    kind: EdgeKind,
+}
+
+#[derive(Debug, Default)]
+struct Graph {
+    nodes: Vec<Node>,
+    index: HashMap<(NodeKind, String), usize>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    fn node(&mut self, kind: NodeKind, id: String, fir: Option<String>) -> usize {
+        if let Some(&index) = self.index.get(&(kind, id.clone())) {
+            return index;
+        }
+        let index = self.nodes.len();
+        self.index.insert((kind, id.clone()), index);
+        self.nodes.push(Node { kind, id, fir });
+        index
+    }
+
+    fn edge(&mut self, from: usize, to: usize, kind: EdgeKind) {
+        self.edges.push(Edge { from, to, kind });
+    }
+
+    /// Node indices grouped by FIR.
+    fn clusters(&self) -> Vec<(Option<&str>, Vec<usize>)> {
+        let mut clusters: Vec<(Option<&str>, Vec<usize>)> = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            let fir = node.fir.as_deref();
+            match clusters.iter_mut().find(|(f, _)| *f == fir) {
+                Some((_, nodes)) => nodes.push(i),
+                None => clusters.push((fir, vec![i])),
+            }
+        }
+        // The first node belongs to the exported FIR, FIRs of referenced nodes follow in
+        // dataset order, undefined nodes go last.
+        let first = self.nodes.first().and_then(|n| n.fir.as_deref());
+        clusters.sort_by_key(|(fir, _)| (fir.is_none(), *fir != first, *fir));
+        clusters
+    }
+
+    fn dot(&self) -> String {
+        let quote =
+            |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut out = String::from("digraph vacs {\n    rankdir=LR;\n");
+        for (fir, nodes) in self.clusters() {
+            let indent = if fir.is_some() { "        " } else { "    " };
+            if let Some(fir) = fir {
+                let _ = writeln!(out, "    subgraph {} {{", quote(&format!("cluster_{fir}")));
+                let _ = writeln!(out, "        label={};", quote(fir));
+            }
+            for i in nodes {
+                let node = &self.nodes[i];
+                let shape = match node.kind {
+                    NodeKind::Station => "ellipse",
+                    NodeKind::Position => "box",
+                    NodeKind::Profile => "note",
+                };
+                let style = if node.fir.is_none() {
+                    ", style=dashed, color=red"
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    out,
+                    "{indent}n{i} [label={}, shape={shape}{style}];",
+                    quote(&node.id)
+                );
+            }
+            if fir.is_some() {
+                out.push_str("    }\n");
+            }
+        }
+        for edge in &self.edges {
+            let attributes = match edge.kind {
+                EdgeKind::Parent => "label=\"parent\", style=bold".to_string(),
+                EdgeKind::ControlledBy(rank) => format!("label=\"{rank}\""),
+                EdgeKind::Profile => "style=dashed".to_string(),
+            };
+            let _ = writeln!(out, "    n{} -> n{} [{attributes}];", edge.from, edge.to);
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn mermaid(&self) -> String {
+        let quote = |value: &str| format!("\"{}\"", value.replace('"', "#quot;"));
+        let mut out = String::from("flowchart LR\n");
+        let mut undefined = Vec::new();
+        for (fir, nodes) in self.clusters() {
+            let indent = if fir.is_some() { "        " } else { "    " };
+            if let Some(fir) = fir {
+                let id = fir.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+                let _ = writeln!(out, "    subgraph fir_{id} [{}]", quote(fir));
+            }
+            for i in nodes {
+                let node = &self.nodes[i];
+                let label = quote(&node.id);
+                let shape = match node.kind {
+                    NodeKind::Station => format!("([{label}])"),
+                    NodeKind::Position => format!("[{label}]"),
+                    NodeKind::Profile => format!("[[{label}]]"),
+                };
+                let _ = writeln!(out, "{indent}n{i}{shape}");
+                if node.fir.is_none() {
+                    undefined.push(format!("n{i}"));
+                }
+            }
+            if fir.is_some() {
+                out.push_str("    end\n");
+            }
+        }
+        for edge in &self.edges {
+            let arrow = match edge.kind {
+                EdgeKind::Parent => "==>|parent|".to_string(),
+                EdgeKind::ControlledBy(rank) => format!("-->|{rank}|"),
+                EdgeKind::Profile => "-.->".to_string(),
+            };
+            let _ = writeln!(out, "    n{} {arrow} n{}", edge.from, edge.to);
+        }
+        if !undefined.is_empty() {
+            out.push_str("    classDef undefined stroke:#d00,stroke-dasharray:4\n");
+            let _ = writeln!(out, "    class {} undefined", undefined.join(","));
+        }
+        out
+    }
+}
+
+/// Exports the stations of a FIR, or of the whole dataset, with their `parent_id` links, the
+/// positions of their `controlled_by` lists ranked in order, and the profiles referencing them.
+/// IDs referenced but not defined anywhere are highlighted.
+pub fn export(
+    input: &Path,
+    fir: Option<&str>,
+    format: GraphFormat,
+    output: Option<&Path>,
+) -> crate::Result<()> {
+    crate::check_input_dir(input)?;
+    let dataset = Dataset::load(input)?;
+    let firs = match fir {
+        Some(id) => match dataset.fir(id) {
+            Some(fir) => vec![fir],
+            None => {
+                return Err(crate::Error::UnknownFir {
+                    fir: id.to_string(),
+                    input: input.to_path_buf(),
+                });
+            }
+        },
+        None => dataset.firs.iter().collect(),
+    };
+
+    let mut station_firs = HashMap::new();
+    let mut position_firs = HashMap::new();
+    for fir in &dataset.firs {
+        station_firs.extend(fir.stations.iter().map(|s| (s.id.to_string(), &fir.id)));
+        position_firs.extend(fir.positions.iter().map(|p| (p.id.to_string(), &fir.id)));
+    }
+    let station_fir = |id: &str| station_firs.get(id).map(|fir| fir.to_string());
+    let position_fir = |id: &str| position_firs.get(id).map(|fir| fir.to_string());
+
+    let mut graph = Graph::default();
+    for fir in &firs {
+        for station in &fir.stations {
+            let from = graph.node(
+                NodeKind::Station,
+                station.id.to_string(),
+                Some(fir.id.clone()),
+            );
+            if let Some(parent) = &station.parent_id {
+                let parent = parent.to_string();
+                let to = graph.node(NodeKind::Station, parent.clone(), station_fir(&parent));
+                graph.edge(from, to, EdgeKind::Parent);
+            }
+            for (rank, position) in station.controlled_by.iter().enumerate() {
+                let position = position.to_string();
+                let to = graph.node(
+                    NodeKind::Position,
+                    position.clone(),
+                    position_fir(&position),
+                );
+                graph.edge(from, to, EdgeKind::ControlledBy(rank + 1));
+            }
+        }
+
+        for (_, profile) in profile::load(fir)? {
+            let from = graph.node(
+                NodeKind::Profile,
+                profile.id().to_string(),
+                Some(fir.id.clone()),
+            );
+            for station in profile.station_ids() {
+                let station = station.to_string();
+                let to = graph.node(NodeKind::Station, station.clone(), station_fir(&station));
+                graph.edge(from, to, EdgeKind::Profile);
+            }
+        }
+    }
+
+    let content = match format {
+        GraphFormat::Dot => graph.dot(),
+        GraphFormat::Mermaid => graph.mermaid(),
+    };
+    crate::write_output(output, &content)
+}
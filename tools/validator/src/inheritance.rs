@@ -0,0 +1,163 @@
+use crate::dataset::{Dataset, Fir};
+use std::collections::{HashMap, HashSet};
+use vacs_protocol::vatsim::StationId;
+use vacs_vatsim::coverage::station::StationRaw;
+
+/// Why an inheritance chain ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainEnd<'a> {
+    /// The last station has no `parent_id`.
+    Root,
+    /// The last station's parent does not exist.
+    MissingParent(&'a StationId),
+    /// The last station's parent is already part of the chain.
+    Cycle(&'a StationId),
+}
+
+/// A station followed by all of its ancestors.
+#[derive(Debug)]
+pub struct Chain<'a> {
+    pub stations: Vec<&'a StationRaw>,
+    pub end: ChainEnd<'a>,
+}
+
+impl<'a> Chain<'a> {
+    /// Follows the `parent_id` references of a station, stopping at missing parents or at the
+    /// first station already visited. The chain is empty if the station does not exist.
+    #[must_use]
+    pub fn resolve(stations: &HashMap<&StationId, &'a StationRaw>, id: &StationId) -> Self {
+        let mut chain = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = stations.get(id).copied();
+
+        while let Some(station) = current {
+            visited.insert(&station.id);
+            chain.push(station);
+
+            let Some(parent) = &station.parent_id else {
+                break;
+            };
+            match stations.get(parent).copied() {
+                Some(parent) if visited.contains(&parent.id) => {
+                    return Self {
+                        stations: chain,
+                        end: ChainEnd::Cycle(&parent.id),
+                    };
+                }
+                Some(parent) => current = Some(parent),
+                None => {
+                    return Self {
+                        stations: chain,
+                        end: ChainEnd::MissingParent(parent),
+                    };
+                }
+            }
+        }
+
+        Self {
+            stations: chain,
+            end: ChainEnd::Root,
+        }
+    }
+}
+
+/// A `parent_id` chain leading back to its first station, which would truncate the coverage
+/// list of every station in or inheriting from it.
+#[derive(Debug)]
+pub struct Cycle<'a> {
+    /// Stations of the cycle in inheritance order, starting with the smallest ID.
+    pub stations: Vec<&'a StationRaw>,
+}
+
+impl Cycle<'_> {
+    /// The station whose `parent_id` closes the cycle.
+    #[must_use]
+    pub fn last(&self) -> &StationRaw {
+        self.stations[self.stations.len() - 1]
+    }
+
+    /// Full path of the cycle, e.g. `A → B → C → A`.
+    #[must_use]
+    pub fn path(&self) -> String {
+        self.stations
+            .iter()
+            .chain(self.stations.first())
+            .map(|s| format!("`{}`", s.id))
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+}
+
+/// Finds all inheritance cycles across the whole dataset, each reported once regardless of how
+/// many chains lead into it.
+#[must_use]
+pub fn find_cycles(dataset: &Dataset) -> Vec<Cycle<'_>> {
+    let stations = dataset.station_map();
+    let mut ids = stations.keys().copied().collect::<Vec<_>>();
+    ids.sort();
+
+    let mut cycles = Vec::new();
+    let mut done: HashSet<&StationId> = HashSet::new();
+    for id in ids {
+        let mut path: Vec<&StationRaw> = Vec::new();
+        let mut index: HashMap<&StationId, usize> = HashMap::new();
+        let mut current = stations.get(id).copied();
+
+        while let Some(station) = current {
+            if done.contains(&station.id) {
+                break;
+            }
+            if let Some(&start) = index.get(&station.id) {
+                let mut cycle = path[start..].to_vec();
+                let min = (0..cycle.len())
+                    .min_by_key(|&i| &cycle[i].id)
+                    .unwrap_or_default();
+                cycle.rotate_left(min);
+                cycles.push(Cycle { stations: cycle });
+                break;
+            }
+            index.insert(&station.id, path.len());
+            path.push(station);
+            current = station
+                .parent_id
+                .as_ref()
+                .and_then(|parent| stations.get(parent).copied());
+        }
+
+        done.extend(path.iter().map(|s| &s.id));
+    }
+
+    cycles.sort_by(|a, b| a.stations[0].id.cmp(&b.stations[0].id));
+    cycles
+}
+
+/// A station inheriting coverage from a parent defined in another FIR directory.
+#[derive(Debug)]
+pub struct CrossFirParent<'a> {
+    pub station: &'a StationRaw,
+    pub fir: &'a Fir,
+    pub parent: &'a StationRaw,
+    pub parent_fir: &'a Fir,
+}
+
+/// Finds all `parent_id` references crossing FIR directories, ordered by station ID.
+#[must_use]
+pub fn find_cross_fir_parents(dataset: &Dataset) -> Vec<CrossFirParent<'_>> {
+    let stations = dataset.station_map();
+    let mut found = dataset
+        .stations()
+        .filter_map(|station| {
+            let parent = stations.get(station.parent_id.as_ref()?).copied()?;
+            let fir = dataset.fir_of_station(&station.id)?;
+            let parent_fir = dataset.fir_of_station(&parent.id)?;
+            (fir.id != parent_fir.id).then_some(CrossFirParent {
+                station,
+                fir,
+                parent,
+                parent_fir,
+            })
+        })
+        .collect::<Vec<_>>();
+    found.sort_by(|a, b| a.station.id.cmp(&b.station.id));
+    found
+}
@@ -1,59 +1,322 @@
-use std::path::PathBuf;
+pub mod ambiguity;
+pub mod codes;
+pub mod config;
+pub mod coverage;
+pub mod datafeed;
+pub mod dataset;
+mod error;
+pub mod explain;
+pub mod graph;
+pub mod inheritance;
+pub mod lint;
+pub mod profile;
+pub mod render;
+pub mod report;
+pub mod source;
+pub mod staffing;
+pub mod suggest;
+pub mod summary;
 
-use vacs_data_diagnostics::log;
+use std::path::{Path, PathBuf};
+
+pub use error::{Error, Result};
+use source::{EntityKind, Lookup, Needle, SourceFile, SourceMap};
+use std::collections::HashMap;
+use vacs_data_diagnostics::{Diagnostic, Severity, log};
 use vacs_vatsim::coverage::CoverageError;
 use vacs_vatsim::coverage::network::Network;
 
-pub fn validate(input: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+/// Validates the dataset and runs all lint rules, returning the highest severity found.
+///
+/// Returns an error if the dataset could not be loaded. Structural and reference errors found
+/// while loading the network are reported as diagnostics before returning [`Error::Dataset`].
+///
+/// If a summary path is given, a Markdown summary of the run is appended to it.
+pub fn validate(
+    input: &PathBuf,
+    config: &config::Config,
+    summary: Option<&Path>,
+) -> Result<Option<Severity>> {
     log::info(format_args!("Validating dataset: {input:?}"));
 
+    check_input_dir(input)?;
+    declare_checks(input);
+
+    let unknown_rules = lint::unknown_rules(config);
+    for rule in &unknown_rules {
+        log::emit(
+            &Diagnostic::new(
+                Severity::Error,
+                format!(
+                    "unknown rule `{rule}` in lint configuration {:?}",
+                    config
+                        .path
+                        .as_deref()
+                        .unwrap_or(std::path::Path::new(config::CONFIG_FILE_NAME))
+                ),
+            )
+            .with_code(codes::UNKNOWN_RULE),
+        );
+    }
+
+    let network = match Network::load_from_dir(input) {
+        Ok(network) => network,
+        Err(errors) => {
+            let diagnostics = report_errors(input, &errors);
+            if let Some(path) = summary {
+                summary::write(path, input, &diagnostics, unknown_rules.len())?;
+            }
+            return Err(Error::Dataset(diagnostics.len() + unknown_rules.len()));
+        }
+    };
+
+    let dataset = dataset::Dataset::load(input)?;
+    let mut sources = SourceMap::new(input);
+    let mut diagnostics = check_cycles(&dataset, &mut sources);
+    let profiles = lint::GeoProfile::load_all(&dataset);
+    let geo = profiles
+        .iter()
+        .map(lint::GeoProfile::layout)
+        .collect::<Vec<_>>();
+    diagnostics.extend(lint::run(
+        &lint::LintContext {
+            network: &network,
+            dataset: &dataset,
+            geo: &geo,
+        },
+        config,
+        &mut sources,
+    ));
+    lint::report(&diagnostics);
+    if let Some(path) = summary {
+        summary::write(path, input, &diagnostics, unknown_rules.len())?;
+    }
+
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+    let errors = count(Severity::Error) + unknown_rules.len();
+    let warnings = count(Severity::Warning);
+    if errors > 0 {
+        log::error(format_args!(
+            "Dataset validation failed with {errors} errors and {warnings} warnings"
+        ));
+    } else if warnings > 0 {
+        log::info(format_args!(
+            "Dataset validation successful with {warnings} warnings"
+        ));
+    } else {
+        log::info("Dataset validation successful");
+    }
+
+    if !unknown_rules.is_empty() {
+        return Ok(Some(Severity::Error));
+    }
+    Ok(diagnostics.iter().map(|d| d.severity).max())
+}
+
+fn read_to_string(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn check_input_dir(input: &Path) -> Result<()> {
     if !input.exists() {
-        log::error(format_args!("Input {input:?} does not exist"));
-        return Err("Input does not exist".into());
+        return Err(Error::InputNotFound(input.to_path_buf()));
     } else if !input.is_dir() {
-        log::error(format_args!("Input {input:?} is not a directory"));
-        return Err("Input is not a directory".into());
+        return Err(Error::InputNotDirectory(input.to_path_buf()));
     }
+    Ok(())
+}
 
-    match Network::load_from_dir(input) {
-        Ok(_) => {
-            log::info("Dataset validation successful");
+/// Writes rendered output to a file, or to stdout if none is given.
+fn write_output(output: Option<&Path>, content: &str) -> Result<()> {
+    match output {
+        Some(path) => {
+            tracing::debug!("Writing {path:?}");
+            std::fs::write(path, content).map_err(|source| Error::Write {
+                path: path.to_path_buf(),
+                source,
+            })
         }
-        Err(errors) => {
-            for err in errors {
-                let (context, inner_error) = unwind_error(&err);
-
-                if log::is_human() {
-                    use console::style;
-                    use vacs_vatsim::coverage::{StructureError, ValidationError};
-
-                    let msg = match inner_error {
-                        CoverageError::Validation(ValidationError::MissingReference {
-                            field,
-                            ref_id,
-                        }) => {
-                            format!(
-                                "referenced {} {} does not exist",
-                                field,
-                                style(format!("`{ref_id}`")).cyan()
-                            )
-                        }
-                        CoverageError::Structure(StructureError::Duplicate { entity, id }) => {
-                            format!("duplicate {} {}", entity, style(format!("`{id}`")).cyan())
-                        }
-                        _ => inner_error.to_string(),
-                    };
-                    log::error_with_context(&context, msg);
-                } else {
-                    log::error_with_context(&context, inner_error);
+        None => {
+            print!("{content}");
+            Ok(())
+        }
+    }
+}
+
+/// Declares all FIR directories and lint rules, so reports list passing checks as well.
+fn declare_checks(input: &Path) {
+    let firs = SourceMap::new(input).firs();
+    let rules = lint::rules();
+    log::declare_checks(&firs, &rules.iter().map(|r| r.id()).collect::<Vec<_>>());
+}
+
+/// Returns an error for every station inheritance cycle. Unlike lint findings, cycles cannot be
+/// suppressed by the configuration.
+fn check_cycles(dataset: &dataset::Dataset, sources: &mut SourceMap) -> Vec<Diagnostic> {
+    inheritance::find_cycles(dataset)
+        .into_iter()
+        .map(|cycle| {
+            let last = cycle.last();
+            let fir = dataset.fir_of_station(&last.id);
+            let location = fir.and_then(|fir| {
+                sources.locate(&Lookup::new(
+                    &fir.id,
+                    SourceFile::Stations,
+                    Needle::Field {
+                        id: last.id.to_string(),
+                        field: "parent_id".to_string(),
+                    },
+                ))
+            });
+            let context = fir
+                .map(|fir| vec![fir.id.clone(), "stations".to_string()])
+                .unwrap_or_default();
+
+            Diagnostic::new(
+                Severity::Error,
+                format!("circular station inheritance: {}", cycle.path()),
+            )
+            .with_code(codes::CIRCULAR_INHERITANCE)
+            .with_context(context)
+            .with_location(location)
+            .with_label(format!("points back to `{}`", cycle.stations[0].id))
+        })
+        .collect()
+}
+
+/// Reports the errors of loading the network and returns them as diagnostics.
+fn report_errors(input: &Path, errors: &[CoverageError]) -> Vec<Diagnostic> {
+    use vacs_vatsim::coverage::{StructureError, ValidationError};
+
+    let mut sources = SourceMap::new(input);
+    let mut known_ids = HashMap::new();
+    let mut diagnostics = Vec::with_capacity(errors.len());
+    for err in errors {
+        let (context, inner_error) = unwind_error(err);
+
+        let code = match inner_error {
+            CoverageError::Validation(ValidationError::MissingReference { field, .. }) => {
+                match referenced_kind(field) {
+                    Some(EntityKind::Station) if field == "parent_id" => {
+                        codes::UNKNOWN_PARENT_STATION
+                    }
+                    Some(EntityKind::Station) => codes::UNKNOWN_STATION,
+                    Some(EntityKind::Position) => codes::UNKNOWN_POSITION,
+                    Some(EntityKind::Profile) => codes::UNKNOWN_PROFILE,
+                    None => codes::INVALID_ENTRY,
+                }
+            }
+            CoverageError::Validation(ValidationError::Empty { field })
+                if field == "controlled_by" =>
+            {
+                codes::MISSING_CONTROLLED_BY
+            }
+            CoverageError::Validation(ValidationError::InvalidFormat { field, .. })
+                if field == "frequency" =>
+            {
+                codes::INVALID_FREQUENCY
+            }
+            CoverageError::Structure(StructureError::Duplicate { .. }) => codes::DUPLICATE_ID,
+            _ => codes::INVALID_ENTRY,
+        };
+
+        let (needle, label) = match inner_error {
+            CoverageError::Validation(ValidationError::MissingReference { field, ref_id }) => (
+                Needle::Reference {
+                    id: ref_id.to_string(),
+                    entity: None,
+                },
+                Some(format!("{} does not exist", referenced_entity(field))),
+            ),
+            CoverageError::Structure(StructureError::Duplicate { entity, id }) => (
+                Needle::Duplicate(id.to_string()),
+                Some(format!("{entity} already defined")),
+            ),
+            _ => (Needle::File, None),
+        };
+        let location = sources.locate_context(&context, needle);
+
+        let help = match inner_error {
+            CoverageError::Validation(ValidationError::MissingReference { field, ref_id }) => {
+                referenced_kind(field).and_then(|kind| {
+                    let ids = known_ids.entry(kind).or_insert_with(|| sources.ids(kind));
+                    let suggestions =
+                        suggest::suggestions(&ref_id.to_string(), ids.iter().map(String::as_str));
+                    (!suggestions.is_empty()).then(|| {
+                        format!(
+                            "did you mean {}?",
+                            suggestions
+                                .iter()
+                                .map(|s| format!("`{s}`"))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                    })
+                })
+            }
+            _ => None,
+        };
+
+        let msg = if log::is_human() {
+            use console::style;
+
+            match inner_error {
+                CoverageError::Validation(ValidationError::MissingReference { field, ref_id }) => {
+                    format!(
+                        "referenced {} {} does not exist",
+                        field,
+                        style(format!("`{ref_id}`")).cyan()
+                    )
+                }
+                CoverageError::Structure(StructureError::Duplicate { entity, id }) => {
+                    format!("duplicate {} {}", entity, style(format!("`{id}`")).cyan())
                 }
+                _ => inner_error.to_string(),
             }
+        } else {
+            inner_error.to_string()
+        };
 
-            return Err("Dataset validation error".into());
+        let mut diagnostic = Diagnostic::new(Severity::Error, msg)
+            .with_code(code)
+            .with_context(context)
+            .with_location(location);
+        if let Some(label) = label {
+            diagnostic = diagnostic.with_label(label);
+        }
+        if let Some(help) = help {
+            diagnostic = diagnostic.with_help(help);
         }
+        log::emit(&diagnostic);
+        diagnostics.push(diagnostic);
     }
+    diagnostics
+}
 
-    Ok(())
+/// Returns the kind of entity a reference field points to, e.g. `station` for `parent_id`.
+fn referenced_entity(field: &str) -> &str {
+    match field {
+        "controlled_by" => "position",
+        "parent_id" => "station",
+        _ => field.strip_suffix("_id").unwrap_or(field),
+    }
+}
+
+fn referenced_kind(field: &str) -> Option<EntityKind> {
+    match referenced_entity(field) {
+        "station" => Some(EntityKind::Station),
+        "position" => Some(EntityKind::Position),
+        "profile" => Some(EntityKind::Profile),
+        _ => None,
+    }
 }
 
 fn unwind_error(error: &CoverageError) -> (Vec<String>, &CoverageError) {
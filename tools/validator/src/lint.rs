@@ -0,0 +1,236 @@
+mod ambiguous_positions;
+mod cross_fir_inheritance;
+mod geo_button_size;
+mod geo_node_overflow;
+mod geo_percentage_sum;
+
+use crate::config::{Config, Level};
+use crate::dataset::Dataset;
+use crate::profile::{self, GeoContainer, Profile};
+use crate::render::layout::{self, GeoBox};
+use crate::source::{Lookup, Needle, SourceFile, SourceMap};
+use vacs_data_diagnostics::{Diagnostic, Severity, log};
+use vacs_vatsim::coverage::network::Network;
+
+/// Everything a lint rule can inspect.
+pub struct LintContext<'a> {
+    pub network: &'a Network,
+    pub dataset: &'a Dataset,
+    /// Geo profiles of the dataset, laid out once for all geo layout rules.
+    pub geo: &'a [GeoLayout<'a>],
+}
+
+/// A single problem found by a lint rule, before a severity has been assigned.
+#[derive(Debug, Clone)]
+pub struct Finding {
+    /// FIR directory the finding belongs to, used for per-FIR severity overrides.
+    pub fir: Option<String>,
+    /// IDs of the stations, positions or profiles involved, used for allow-lists.
+    pub entities: Vec<String>,
+    pub context: Vec<String>,
+    /// Where in the dataset files the finding should be reported.
+    pub lookup: Option<Lookup>,
+    /// Short note shown next to the source snippet.
+    pub label: Option<String>,
+    pub help: Option<String>,
+    pub message: String,
+}
+
+impl Finding {
+    #[must_use]
+    pub fn new(context: Vec<String>, message: impl Into<String>) -> Self {
+        Self {
+            fir: context.first().cloned(),
+            entities: Vec::new(),
+            context,
+            lookup: None,
+            label: None,
+            help: None,
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_entities(mut self, entities: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.entities = entities.into_iter().map(|e| e.to_string()).collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_lookup(mut self, lookup: Lookup) -> Self {
+        self.lookup = Some(lookup);
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+}
+
+pub trait Lint {
+    /// Stable, kebab-case identifier of the rule.
+    fn id(&self) -> &'static str;
+
+    /// Stable code of the rule, see [`crate::codes`].
+    fn code(&self) -> &'static str;
+
+    /// One-line description of what the rule checks.
+    fn description(&self) -> &'static str;
+
+    fn default_severity(&self) -> Severity;
+
+    fn check(&self, ctx: &LintContext<'_>) -> Vec<Finding>;
+}
+
+/// Returns all available lint rules.
+#[must_use]
+pub fn rules() -> Vec<Box<dyn Lint>> {
+    vec![
+        Box::new(ambiguous_positions::AmbiguousPositions),
+        Box::new(cross_fir_inheritance::CrossFirInheritance),
+        Box::new(geo_button_size::GeoButtonSize),
+        Box::new(geo_percentage_sum::GeoPercentageSum),
+        Box::new(geo_node_overflow::GeoNodeOverflow),
+    ]
+}
+
+/// A geo profile and the FIR directory it is defined in, as checked by the geo layout rules.
+pub struct GeoProfile {
+    fir: String,
+    /// File stem of the profile file.
+    file: String,
+    id: String,
+    root: GeoContainer,
+}
+
+impl GeoProfile {
+    /// Geo profiles of all FIRs. Profiles that cannot be read are skipped, the network
+    /// validation reports them.
+    #[must_use]
+    pub fn load_all(dataset: &Dataset) -> Vec<Self> {
+        let mut profiles = Vec::new();
+        for fir in &dataset.firs {
+            let Ok(loaded) = profile::load(fir) else {
+                continue;
+            };
+            for (path, profile) in loaded {
+                if let Profile::Geo { id, root } = profile {
+                    profiles.push(Self {
+                        fir: fir.id.clone(),
+                        file: path
+                            .file_stem()
+                            .map(|s| s.to_string_lossy().into_owned())
+                            .unwrap_or_else(|| id.clone()),
+                        id,
+                        root,
+                    });
+                }
+            }
+        }
+        profiles
+    }
+
+    /// Lays out the profile within the reference page area.
+    #[must_use]
+    pub fn layout(&self) -> GeoLayout<'_> {
+        GeoLayout {
+            profile: self,
+            root: layout::geo(&self.root),
+        }
+    }
+
+    /// Creates a finding pointing at the node at `path` of the profile, as passed by
+    /// [`GeoBox::visit`].
+    fn finding(&self, path: &str, message: impl Into<String>) -> Finding {
+        Finding::new(
+            vec![self.fir.clone(), "profiles".to_string(), self.id.clone()],
+            message,
+        )
+        .with_entities([&self.id])
+        .with_lookup(Lookup::new(
+            &self.fir,
+            SourceFile::Profile(self.file.clone()),
+            Needle::Path(path.to_string()),
+        ))
+    }
+}
+
+/// A geo profile laid out within the reference page area.
+pub struct GeoLayout<'a> {
+    profile: &'a GeoProfile,
+    root: GeoBox<'a>,
+}
+
+/// Tolerance in pixels when comparing laid out sizes. Sizes are computed from `rem`, so boxes
+/// that fit exactly may still be off by rounding errors.
+const LAYOUT_TOLERANCE: f64 = 0.5;
+
+/// Runs all lint rules and returns their diagnostics, ordered by rule. Severity overrides and
+/// allow-lists from the configuration are applied to every finding.
+pub fn run(ctx: &LintContext<'_>, config: &Config, sources: &mut SourceMap) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for rule in rules() {
+        for finding in rule.check(ctx) {
+            if config.is_allowed(rule.id(), &finding.entities) {
+                tracing::trace!(
+                    "Suppressing {} finding for {} (allow-listed)",
+                    rule.id(),
+                    finding.entities.join(", ")
+                );
+                continue;
+            }
+
+            let severity = match config.level(rule.id(), finding.fir.as_deref()) {
+                Some(Level::Off) => {
+                    tracing::trace!(
+                        "Suppressing {} finding for {} (rule is off)",
+                        rule.id(),
+                        finding.entities.join(", ")
+                    );
+                    continue;
+                }
+                Some(Level::Severity(severity)) => severity,
+                None => rule.default_severity(),
+            };
+
+            let location = finding.lookup.and_then(|l| sources.locate(&l));
+            let mut diagnostic = Diagnostic::new(severity, finding.message)
+                .with_code(rule.code())
+                .with_rule(rule.id())
+                .with_location(location);
+            diagnostic.fir = finding.fir;
+            diagnostic.label = finding.label;
+            diagnostic.help = finding.help;
+            diagnostics.push(diagnostic.with_context(finding.context));
+        }
+    }
+
+    diagnostics
+}
+
+/// Returns the names of all configured rules that do not exist.
+#[must_use]
+pub fn unknown_rules(config: &Config) -> Vec<&str> {
+    let known = rules().iter().map(|r| r.id()).collect::<Vec<_>>();
+    config
+        .rules
+        .keys()
+        .map(String::as_str)
+        .filter(|name| !known.contains(name))
+        .collect()
+}
+
+pub fn report(diagnostics: &[Diagnostic]) {
+    for d in diagnostics {
+        log::emit(d);
+    }
+}
@@ -0,0 +1,107 @@
+use super::{Finding, Lint, LintContext};
+use crate::ambiguity::{AmbiguousGroup, find_ambiguous_groups};
+use crate::codes;
+use crate::source::{Lookup, Needle, SourceFile};
+use vacs_data_diagnostics::Severity;
+
+/// Positions sharing frequency and facility type with overlapping prefixes.
+pub struct AmbiguousPositions;
+
+impl Lint for AmbiguousPositions {
+    fn id(&self) -> &'static str {
+        "ambiguous-positions"
+    }
+
+    fn code(&self) -> &'static str {
+        codes::AMBIGUOUS_POSITIONS
+    }
+
+    fn description(&self) -> &'static str {
+        "positions a single non-exact callsign could match at the same time"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>) -> Vec<Finding> {
+        find_ambiguous_groups(ctx.dataset)
+            .into_iter()
+            .map(|group| {
+                let ids = group
+                    .positions
+                    .iter()
+                    .map(|p| format!("`{}`", p.id))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let prefixes = group
+                    .positions
+                    .iter()
+                    .map(|p| {
+                        let mut prefixes =
+                            p.prefixes.iter().map(String::as_str).collect::<Vec<_>>();
+                        prefixes.sort_unstable();
+                        format!("{} [{}]", p.id, prefixes.join(", "))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                let fir = ctx.dataset.fir_of_position(&group.positions[0].id);
+                let context = fir
+                    .map(|fir| vec![fir.id.clone(), "positions".to_string()])
+                    .unwrap_or_default();
+
+                let finding = Finding::new(
+                    context,
+                    format!(
+                        "positions {ids} share frequency {} and facility type {} with overlapping prefixes ({prefixes}), so callsigns starting with `{}` are ambiguous",
+                        group.frequency,
+                        group.positions[0].facility_type.as_str(),
+                        group.prefix,
+                    ),
+                )
+                .with_entities(group.positions.iter().map(|p| &p.id))
+                .with_help(unique_callsigns(&group))
+                .with_label(format!(
+                    "overlaps with {}",
+                    group.positions[1..]
+                        .iter()
+                        .map(|p| format!("`{}`", p.id))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+
+                match fir {
+                    Some(fir) => finding.with_lookup(Lookup::new(
+                        &fir.id,
+                        SourceFile::Positions,
+                        Needle::Id(group.positions[0].id.to_string()),
+                    )),
+                    None => finding,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Describes the callsigns that still resolve to a single position of the group: the exact
+/// position IDs and callsigns starting with a prefix no other position overlaps with.
+fn unique_callsigns(group: &AmbiguousGroup<'_>) -> String {
+    let exact = group
+        .positions
+        .iter()
+        .map(|p| format!("`{}`", p.id))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if group.unique.is_empty() {
+        return format!("only the exact callsigns {exact} resolve uniquely");
+    }
+
+    let prefixes = group
+        .unique
+        .iter()
+        .map(|(prefix, position)| format!("`{prefix}` (`{}`)", position.id))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("the exact callsigns {exact} and callsigns starting with {prefixes} resolve uniquely")
+}
@@ -0,0 +1,51 @@
+use super::{Finding, Lint, LintContext};
+use crate::codes;
+use crate::inheritance::find_cross_fir_parents;
+use crate::source::{Lookup, Needle, SourceFile};
+use vacs_data_diagnostics::Severity;
+
+/// Stations whose `parent_id` refers to a station of another FIR directory.
+pub struct CrossFirInheritance;
+
+impl Lint for CrossFirInheritance {
+    fn id(&self) -> &'static str {
+        "cross-fir-inheritance"
+    }
+
+    fn code(&self) -> &'static str {
+        codes::CROSS_FIR_INHERITANCE
+    }
+
+    fn description(&self) -> &'static str {
+        "stations inheriting coverage from a station of another FIR"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>) -> Vec<Finding> {
+        find_cross_fir_parents(ctx.dataset)
+            .into_iter()
+            .map(|found| {
+                Finding::new(
+                    vec![found.fir.id.clone(), "stations".to_string()],
+                    format!(
+                        "station `{}` inherits coverage from `{}` defined in FIR `{}`",
+                        found.station.id, found.parent.id, found.parent_fir.id
+                    ),
+                )
+                .with_entities([&found.station.id, &found.parent.id])
+                .with_lookup(Lookup::new(
+                    &found.fir.id,
+                    SourceFile::Stations,
+                    Needle::Field {
+                        id: found.station.id.to_string(),
+                        field: "parent_id".to_string(),
+                    },
+                ))
+                .with_label(format!("defined in FIR `{}`", found.parent_fir.id))
+            })
+            .collect()
+    }
+}
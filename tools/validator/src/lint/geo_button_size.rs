@@ -0,0 +1,62 @@
+use super::{Finding, LAYOUT_TOLERANCE, Lint, LintContext};
+use crate::codes;
+use crate::render::layout::{self, GeoItem};
+use vacs_data_diagnostics::Severity;
+
+/// Geo buttons smaller than their label once the profile is laid out.
+pub struct GeoButtonSize;
+
+impl Lint for GeoButtonSize {
+    fn id(&self) -> &'static str {
+        "geo-button-too-small"
+    }
+
+    fn code(&self) -> &'static str {
+        codes::GEO_BUTTON_TOO_SMALL
+    }
+
+    fn description(&self) -> &'static str {
+        "geo profile buttons too small to display their label"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for geo in ctx.geo {
+            let profile = geo.profile;
+            geo.root.visit(&mut |path, _, node| {
+                let GeoItem::Button(button) = node.item else {
+                    return;
+                };
+                let (width, height) = layout::label_size(&button.label);
+                if node.rect.width + LAYOUT_TOLERANCE >= width
+                    && node.rect.height + LAYOUT_TOLERANCE >= height
+                {
+                    return;
+                }
+
+                let lines = button.label.0.len();
+                findings.push(
+                    profile
+                        .finding(
+                            path,
+                            format!(
+                                "button `{}` at `{path}` of profile `{}` is {:.0}×{:.0}px, too small for its {lines}-line label (needs {:.0}×{:.0}px)",
+                                button.label.0.join(" "),
+                                profile.id,
+                                node.rect.width,
+                                node.rect.height,
+                                width,
+                                height
+                            ),
+                        )
+                        .with_label(format!("label needs {width:.0}×{height:.0}px")),
+                );
+            });
+        }
+        findings
+    }
+}
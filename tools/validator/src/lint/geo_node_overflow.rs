@@ -0,0 +1,72 @@
+use super::{Finding, LAYOUT_TOLERANCE, Lint, LintContext};
+use crate::codes;
+use crate::render::layout::GeoItem;
+use vacs_data_diagnostics::Severity;
+
+/// Geo nodes laid out beyond the bounds of their container.
+pub struct GeoNodeOverflow;
+
+impl Lint for GeoNodeOverflow {
+    fn id(&self) -> &'static str {
+        "geo-node-overflow"
+    }
+
+    fn code(&self) -> &'static str {
+        codes::GEO_NODE_OVERFLOW
+    }
+
+    fn description(&self) -> &'static str {
+        "geo profile nodes pushed outside their container"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for geo in ctx.geo {
+            let profile = geo.profile;
+            geo.root.visit(&mut |path, parent, node| {
+                let Some(parent) = parent else {
+                    return;
+                };
+                let (outer, inner) = (parent.rect, node.rect);
+                let overflow = [
+                    outer.x - inner.x,
+                    outer.y - inner.y,
+                    inner.right() - outer.right(),
+                    inner.bottom() - outer.bottom(),
+                ]
+                .into_iter()
+                .fold(0.0, f64::max);
+                // Dividers are meant to extend beyond their container by their oversize.
+                let overflow = match node.item {
+                    GeoItem::Divider(divider) => overflow - divider.oversize.unwrap_or_default(),
+                    _ => overflow,
+                };
+                if overflow < LAYOUT_TOLERANCE {
+                    return;
+                }
+
+                let node = match node.item {
+                    GeoItem::Container(_) => format!("container `{path}`"),
+                    GeoItem::Button(button) => {
+                        format!("button `{}` at `{path}`", button.label.0.join(" "))
+                    }
+                    GeoItem::Divider(_) => format!("divider `{path}`"),
+                };
+                let message = format!(
+                    "{node} of profile `{}` extends {overflow:.0}px outside its container",
+                    profile.id
+                );
+                findings.push(
+                    profile
+                        .finding(path, message)
+                        .with_label(format!("{overflow:.0}px outside its container")),
+                );
+            });
+        }
+        findings
+    }
+}
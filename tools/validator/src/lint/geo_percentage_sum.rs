@@ -0,0 +1,73 @@
+use super::{Finding, Lint, LintContext};
+use crate::codes;
+use crate::profile::{FlexDirection, GeoNode};
+use crate::render::layout::{self, GeoItem};
+use vacs_data_diagnostics::Severity;
+
+/// Containers whose children claim more than the whole main axis in percentages.
+pub struct GeoPercentageSum;
+
+impl Lint for GeoPercentageSum {
+    fn id(&self) -> &'static str {
+        "geo-percentage-sum"
+    }
+
+    fn code(&self) -> &'static str {
+        codes::GEO_PERCENTAGE_SUM
+    }
+
+    fn description(&self) -> &'static str {
+        "geo profile containers whose children's percentage sizes sum above 100%"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for geo in ctx.geo {
+            let profile = geo.profile;
+            geo.root.visit(&mut |path, _, node| {
+                let GeoItem::Container(container) = node.item else {
+                    return;
+                };
+                let axis = match container.direction {
+                    FlexDirection::Row => "width",
+                    FlexDirection::Col => "height",
+                };
+                let sum = container
+                    .children
+                    .iter()
+                    .filter_map(|child| match child {
+                        GeoNode::Container(child) => match container.direction {
+                            FlexDirection::Row => child.width.as_deref(),
+                            FlexDirection::Col => child.height.as_deref(),
+                        },
+                        GeoNode::Button(_) | GeoNode::Divider(_) => None,
+                    })
+                    .filter_map(layout::percentage)
+                    .sum::<f64>();
+                if sum <= 100.0 {
+                    return;
+                }
+
+                let container = if path.is_empty() {
+                    "root container".to_string()
+                } else {
+                    format!("container `{path}`")
+                };
+                let message = format!(
+                    "children of {container} of profile `{}` take up {sum}% of its {axis}",
+                    profile.id
+                );
+                findings.push(
+                    profile
+                        .finding(path, message)
+                        .with_label(format!("children sum up to {sum}% of the {axis}")),
+                );
+            });
+        }
+        findings
+    }
+}
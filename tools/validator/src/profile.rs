@@ -0,0 +1,223 @@
+//! Profile definitions of the dataset, modelled after `docs/schemas/profiles.schema.json`.
+//!
+//! The validated profiles of [`Network`](vacs_vatsim::coverage::network::Network) are not
+//! exposed, so tools rendering or analysing profiles read the raw files again. Unknown fields are
+//! ignored, the schema is enforced by the network validation.
+
+use crate::dataset::Fir;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use vacs_protocol::vatsim::StationId;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum Profile {
+    Tabbed {
+        id: String,
+        tabs: Vec<Tab>,
+    },
+    Geo {
+        id: String,
+        #[serde(flatten)]
+        root: GeoContainer,
+    },
+}
+
+impl Profile {
+    #[must_use]
+    pub fn id(&self) -> &str {
+        match self {
+            Profile::Tabbed { id, .. } | Profile::Geo { id, .. } => id,
+        }
+    }
+
+    /// Every key of the profile, including keys of nested subpages, in document order.
+    #[must_use]
+    pub fn keys(&self) -> Vec<&DirectAccessKey> {
+        let mut keys = Vec::new();
+        match self {
+            Profile::Tabbed { tabs, .. } => {
+                for tab in tabs {
+                    tab.page.collect_keys(&mut keys);
+                }
+            }
+            Profile::Geo { root, .. } => root.collect_keys(&mut keys),
+        }
+        keys
+    }
+
+    /// Stations referenced by keys of the profile, deduplicated in document order.
+    #[must_use]
+    pub fn station_ids(&self) -> Vec<&StationId> {
+        let mut ids = Vec::new();
+        for id in self
+            .keys()
+            .into_iter()
+            .filter_map(|k| k.station_id.as_ref())
+        {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+}
+
+/// A label of one to three lines, given as a single string or an array of lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "LabelRaw")]
+pub struct Label(pub Vec<String>);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LabelRaw {
+    Line(String),
+    Lines(Vec<String>),
+}
+
+impl From<LabelRaw> for Label {
+    fn from(raw: LabelRaw) -> Self {
+        match raw {
+            LabelRaw::Line(line) => Self(vec![line]),
+            LabelRaw::Lines(lines) => Self(lines),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tab {
+    pub label: Label,
+    pub page: DirectAccessPage,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DirectAccessPage {
+    pub rows: u32,
+    #[serde(default)]
+    pub keys: Vec<DirectAccessKey>,
+    /// Dynamic list of online clients, shown instead of keys.
+    pub client_page: Option<serde_json::Value>,
+}
+
+impl DirectAccessPage {
+    fn collect_keys<'a>(&'a self, keys: &mut Vec<&'a DirectAccessKey>) {
+        for key in &self.keys {
+            keys.push(key);
+            if let Some(page) = &key.page {
+                page.collect_keys(keys);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DirectAccessKey {
+    #[serde(default)]
+    pub label: Label,
+    pub station_id: Option<StationId>,
+    pub page: Option<DirectAccessPage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum GeoNode {
+    Container(GeoContainer),
+    Button(GeoButton),
+    Divider(GeoDivider),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeoContainer {
+    pub direction: FlexDirection,
+    pub children: Vec<GeoNode>,
+    /// Size with unit, e.g. `100%` or `20rem`.
+    pub height: Option<String>,
+    pub width: Option<String>,
+    pub padding: Option<f64>,
+    pub padding_left: Option<f64>,
+    pub padding_right: Option<f64>,
+    pub padding_top: Option<f64>,
+    pub padding_bottom: Option<f64>,
+    pub gap: Option<f64>,
+    pub justify_content: Option<String>,
+    pub align_items: Option<String>,
+}
+
+impl GeoContainer {
+    fn collect_keys<'a>(&'a self, keys: &mut Vec<&'a DirectAccessKey>) {
+        for child in &self.children {
+            match child {
+                GeoNode::Container(container) => container.collect_keys(keys),
+                GeoNode::Button(GeoButton {
+                    page: Some(page), ..
+                }) => page.collect_keys(keys),
+                GeoNode::Button(_) | GeoNode::Divider(_) => {}
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum FlexDirection {
+    #[serde(rename = "row")]
+    Row,
+    #[serde(rename = "col")]
+    Col,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeoButton {
+    pub label: Label,
+    pub size: f64,
+    pub page: Option<DirectAccessPage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeoDivider {
+    pub orientation: DividerOrientation,
+    pub thickness: f64,
+    pub color: String,
+    pub oversize: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum DividerOrientation {
+    #[serde(rename = "horizontal")]
+    Horizontal,
+    #[serde(rename = "vertical")]
+    Vertical,
+}
+
+/// Reads a single profile file.
+pub fn read(path: &Path) -> crate::Result<Profile> {
+    tracing::debug!("Reading {path:?}");
+    let content = crate::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|source| crate::Error::ParseJson {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Reads all profiles of a FIR, ordered by file name.
+pub fn load(fir: &Fir) -> crate::Result<Vec<(PathBuf, Profile)>> {
+    let dir = fir.path.join("profiles");
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = std::fs::read_dir(&dir)
+        .map_err(|source| crate::Error::Read {
+            path: dir.clone(),
+            source,
+        })?
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| read(&path).map(|profile| (path, profile)))
+        .collect()
+}
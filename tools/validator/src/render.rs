@@ -0,0 +1,71 @@
+//! Static previews of profiles as displayed by the vacs client, e.g. for attaching to reviews.
+
+pub mod html;
+pub mod layout;
+pub mod svg;
+
+use crate::dataset::Dataset;
+use crate::profile::{self, Profile};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Width of the page area of the vacs client, in pixels.
+pub const PAGE_WIDTH: f64 = 900.0;
+/// Height of the page area of the vacs client, in pixels.
+pub const PAGE_HEIGHT: f64 = 544.0;
+/// Padding of the page area, in `rem`.
+pub const PAGE_PADDING: f64 = 0.5;
+/// Pixels per `rem`, the unit of all geo profile lengths except divider thickness and oversize.
+pub const REM: f64 = 16.0;
+/// Width of a column of a direct access page, in `rem`.
+pub const KEY_WIDTH: f64 = 6.25;
+/// Gap between the keys of a direct access page, in `rem`.
+pub const KEY_GAP: f64 = 0.5;
+/// Font size of key and button labels, in pixels.
+pub const FONT_SIZE: f64 = 16.0;
+/// Line height of key and button labels, in pixels.
+pub const LINE_HEIGHT: f64 = 20.0;
+/// Advance of a single character of the monospace label font, in pixels.
+pub const CHAR_WIDTH: f64 = 0.6 * FONT_SIZE;
+/// Minimum space between a label and the border of its key, in pixels.
+pub const LABEL_PADDING: f64 = 4.0;
+
+/// Escapes text for HTML and SVG content and attribute values.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Loads the dataset and a profile, given either as path to a profile file or as profile ID.
+fn load(input: &Path, profile: &str) -> crate::Result<(Dataset, Profile)> {
+    crate::check_input_dir(input)?;
+    let dataset = Dataset::load(input)?;
+
+    let path = Path::new(profile);
+    if path.is_file() {
+        let profile = profile::read(path)?;
+        return Ok((dataset, profile));
+    }
+
+    for fir in &dataset.firs {
+        if let Some((_, found)) = profile::load(fir)?
+            .into_iter()
+            .find(|(_, p)| p.id().eq_ignore_ascii_case(profile))
+        {
+            return Ok((dataset, found));
+        }
+    }
+    Err(crate::Error::UnknownProfile {
+        profile: profile.to_string(),
+        input: input.to_path_buf(),
+    })
+}
+
+/// IDs of all stations of the dataset, to highlight keys referencing unknown stations.
+fn station_ids(dataset: &Dataset) -> HashSet<String> {
+    dataset.stations().map(|s| s.id.to_string()).collect()
+}
@@ -0,0 +1,273 @@
+use super::{
+    FONT_SIZE, KEY_GAP, KEY_WIDTH, LABEL_PADDING, LINE_HEIGHT, PAGE_HEIGHT, PAGE_PADDING,
+    PAGE_WIDTH, escape,
+};
+use crate::profile::{
+    DirectAccessKey, DirectAccessPage, DividerOrientation, FlexDirection, GeoContainer, GeoNode,
+    Label, Profile,
+};
+use console::style;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+use vacs_data_diagnostics::log;
+
+fn label(label: &Label) -> String {
+    label
+        .0
+        .iter()
+        .map(|line| escape(line))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+/// Renders the pages of a profile one after another. Keys opening a subpage link to it, the
+/// subpages follow the top-level pages.
+struct Renderer<'a> {
+    stations: &'a HashSet<String>,
+    out: String,
+    /// Subpages waiting to be rendered with their titles.
+    pages: Vec<(String, &'a DirectAccessPage)>,
+    unknown: Vec<(String, String)>,
+}
+
+impl<'a> Renderer<'a> {
+    /// Queues a subpage, returning its anchor.
+    fn subpage(&mut self, title: String, page: &'a DirectAccessPage) -> String {
+        self.pages.push((title, page));
+        format!("page-{}", self.pages.len())
+    }
+
+    fn page(&mut self, title: &str, page: &'a DirectAccessPage) {
+        if let Some(client_page) = &page.client_page {
+            let _ = writeln!(
+                self.out,
+                "<div class=\"client-page\">List of online clients<pre>{}</pre></div>",
+                escape(&serde_json::to_string_pretty(client_page).unwrap_or_default())
+            );
+            return;
+        }
+
+        let _ = writeln!(
+            self.out,
+            "<div class=\"grid\" style=\"grid-template-rows: repeat({}, 1fr)\">",
+            page.rows.max(1)
+        );
+        for key in &page.keys {
+            self.key(title, key);
+        }
+        self.out.push_str("</div>\n");
+    }
+
+    fn key(&mut self, title: &str, key: &'a DirectAccessKey) {
+        let text = label(&key.label);
+        if let Some(page) = &key.page {
+            let anchor = self.subpage(format!("{title} › {}", key.label.0.join(" ")), page);
+            let _ = writeln!(
+                self.out,
+                "<a class=\"key page\" href=\"#{anchor}\">{text}</a>"
+            );
+        } else if let Some(station) = &key.station_id {
+            let station = station.to_string();
+            if self.stations.contains(&station) {
+                let _ = writeln!(
+                    self.out,
+                    "<div class=\"key station\" title=\"{}\">{text}</div>",
+                    escape(&station)
+                );
+            } else {
+                let _ = writeln!(
+                    self.out,
+                    "<div class=\"key station unknown\" title=\"unknown station {}\">{text}</div>",
+                    escape(&station)
+                );
+                self.unknown.push((title.to_string(), station));
+            }
+        } else {
+            let _ = writeln!(self.out, "<div class=\"key disabled\">{text}</div>");
+        }
+    }
+
+    fn container(&mut self, title: &str, container: &'a GeoContainer) {
+        let mut css = format!(
+            "flex-direction: {}",
+            match container.direction {
+                FlexDirection::Row => "row",
+                FlexDirection::Col => "column",
+            }
+        );
+        let rem = |value: Option<f64>| value.map(|v| format!("{v}rem"));
+        let properties = [
+            ("height", container.height.clone()),
+            ("width", container.width.clone()),
+            ("padding", rem(container.padding)),
+            ("padding-left", rem(container.padding_left)),
+            ("padding-right", rem(container.padding_right)),
+            ("padding-top", rem(container.padding_top)),
+            ("padding-bottom", rem(container.padding_bottom)),
+            ("gap", rem(container.gap)),
+            ("justify-content", container.justify_content.clone()),
+            ("align-items", container.align_items.clone()),
+        ];
+        for (property, value) in properties {
+            if let Some(value) = value {
+                let _ = write!(css, "; {property}: {}", escape(&value));
+            }
+        }
+
+        let _ = writeln!(self.out, "<div class=\"container\" style=\"{css}\">");
+        for child in &container.children {
+            match child {
+                GeoNode::Container(container) => self.container(title, container),
+                GeoNode::Button(button) => {
+                    let text = label(&button.label);
+                    let size = format!("width: {0}rem; height: {0}rem", button.size);
+                    match &button.page {
+                        Some(page) => {
+                            let anchor = self
+                                .subpage(format!("{title} › {}", button.label.0.join(" ")), page);
+                            let _ = writeln!(
+                                self.out,
+                                "<a class=\"key button page\" style=\"{size}\" href=\"#{anchor}\">{text}</a>"
+                            );
+                        }
+                        None => {
+                            let _ = writeln!(
+                                self.out,
+                                "<div class=\"key button disabled\" style=\"{size}\">{text}</div>"
+                            );
+                        }
+                    }
+                }
+                GeoNode::Divider(divider) => {
+                    let oversize = divider.oversize.unwrap_or_default();
+                    let css = match divider.orientation {
+                        DividerOrientation::Horizontal => {
+                            format!("height: {}px; margin: 0 -{oversize}px", divider.thickness)
+                        }
+                        DividerOrientation::Vertical => {
+                            format!("width: {}px; margin: -{oversize}px 0", divider.thickness)
+                        }
+                    };
+                    let _ = writeln!(
+                        self.out,
+                        "<div class=\"divider\" style=\"{css}; background: {}\"></div>",
+                        escape(&divider.color)
+                    );
+                }
+            }
+        }
+        self.out.push_str("</div>\n");
+    }
+
+    /// Renders all queued subpages, including subpages queued while rendering them.
+    fn subpages(&mut self) {
+        let mut index = 0;
+        while let Some((title, page)) = self.pages.get(index).cloned() {
+            index += 1;
+            let _ = writeln!(
+                self.out,
+                "<section id=\"page-{index}\">\n<h2>{}</h2>",
+                escape(&title)
+            );
+            self.page(&title, page);
+            self.out.push_str("</section>\n");
+        }
+    }
+}
+
+fn css() -> String {
+    format!(
+        "\
+body {{ margin: 1rem; background: #e5e7eb; color: #111; font-family: ui-monospace, monospace; }}
+h1, h2 {{ font-family: system-ui, sans-serif; }}
+h2 {{ font-size: 1rem; }}
+.unknown-list {{ color: #b00; }}
+nav.tabs {{ display: flex; gap: 0.5rem; margin-bottom: 0.5rem; }}
+nav.tabs a {{ padding: 0.5rem 1rem; background: #d1d5db; color: inherit; text-decoration: none; border: 1px solid #6b7280; border-radius: 4px; }}
+.grid, .frame {{ box-sizing: border-box; width: {PAGE_WIDTH}px; height: {PAGE_HEIGHT}px; background: #b5bac4; outline: 1px solid #6b7280; overflow: hidden; }}
+.grid {{ padding: {PAGE_PADDING}rem; display: grid; grid-auto-flow: column; grid-auto-columns: {KEY_WIDTH}rem; gap: {KEY_GAP}rem; }}
+.frame > .container {{ width: 100%; height: 100%; }}
+.container {{ display: flex; box-sizing: border-box; }}
+.key {{ display: flex; align-items: center; justify-content: center; box-sizing: border-box; text-align: center; font-size: {FONT_SIZE}px; line-height: {LINE_HEIGHT}px; font-weight: bold; padding: {LABEL_PADDING}px; background: #d1d5db; border: 1px solid #6b7280; border-radius: 4px; color: inherit; text-decoration: none; }}
+.key.button {{ flex: none; }}
+.key.page {{ background: #a5e8ff; }}
+.key.disabled {{ color: #6b7280; }}
+.key.unknown {{ background: #fecaca; outline: 3px solid #dc2626; }}
+.divider {{ flex: none; align-self: stretch; }}
+.client-page {{ padding: 1rem; background: #d1d5db; }}
+"
+    )
+}
+
+/// Renders a profile as a self-contained HTML page. Tabbed profiles show a tab bar and the grid
+/// of every tab, geo profiles their flex layout within the page area of the client. Subpages
+/// follow the top-level pages, keys referencing stations missing in the dataset are highlighted.
+pub fn export(input: &Path, profile: &str, output: Option<&Path>) -> crate::Result<()> {
+    let (dataset, profile) = super::load(input, profile)?;
+    let stations = super::station_ids(&dataset);
+
+    let mut renderer = Renderer {
+        stations: &stations,
+        out: String::new(),
+        pages: Vec::new(),
+        unknown: Vec::new(),
+    };
+    let id = escape(profile.id());
+    match &profile {
+        Profile::Tabbed { tabs, .. } => {
+            renderer.out.push_str("<nav class=\"tabs\">\n");
+            for (i, tab) in tabs.iter().enumerate() {
+                let _ = writeln!(
+                    renderer.out,
+                    "<a href=\"#tab-{}\">{}</a>",
+                    i + 1,
+                    label(&tab.label)
+                );
+            }
+            renderer.out.push_str("</nav>\n");
+            for (i, tab) in tabs.iter().enumerate() {
+                let title = tab.label.0.join(" ");
+                let _ = writeln!(
+                    renderer.out,
+                    "<section id=\"tab-{}\">\n<h2>{}</h2>",
+                    i + 1,
+                    escape(&title)
+                );
+                renderer.page(&title, &tab.page);
+                renderer.out.push_str("</section>\n");
+            }
+        }
+        Profile::Geo { id, root } => {
+            let _ = writeln!(renderer.out, "<section>\n<div class=\"frame\">");
+            renderer.container(id, root);
+            renderer.out.push_str("</div>\n</section>\n");
+        }
+    }
+    renderer.subpages();
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{id}</title>\n<style>\n{}</style>\n</head>\n<body>\n<h1>{id}</h1>\n",
+        css()
+    );
+    if !renderer.unknown.is_empty() {
+        html.push_str("<p class=\"unknown-list\">Keys referencing unknown stations:</p>\n<ul class=\"unknown-list\">\n");
+        for (page, station) in &renderer.unknown {
+            let _ = writeln!(
+                html,
+                "<li><code>{}</code> on {}</li>",
+                escape(station),
+                escape(page)
+            );
+            log::warn(format_args!(
+                "Key on page {page} references unknown station {}",
+                style(format!("`{station}`")).cyan()
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+    html.push_str(&renderer.out);
+    html.push_str("</body>\n</html>\n");
+
+    crate::write_output(output, &html)
+}
@@ -0,0 +1,578 @@
+//! Layout of profile pages within the page area of the client, following the CSS grid and
+//! flexbox rules the client displays them with.
+//!
+//! Flex items neither wrap nor grow. Containers shrink down to their content size if their
+//! siblings would overflow otherwise, buttons and dividers never shrink.
+
+use super::{
+    CHAR_WIDTH, KEY_GAP, KEY_WIDTH, LABEL_PADDING, LINE_HEIGHT, PAGE_HEIGHT, PAGE_PADDING,
+    PAGE_WIDTH, REM,
+};
+use crate::profile::{
+    DirectAccessPage, DividerOrientation, FlexDirection, GeoButton, GeoContainer, GeoDivider,
+    GeoNode, Label,
+};
+
+/// A box in pixels, relative to the top left corner of the page area.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    #[must_use]
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    #[must_use]
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    fn inset(&self, top: f64, right: f64, bottom: f64, left: f64) -> Self {
+        Self {
+            x: self.x + left,
+            y: self.y + top,
+            width: self.width - left - right,
+            height: self.height - top - bottom,
+        }
+    }
+}
+
+/// The whole page area of the client.
+#[must_use]
+pub fn page() -> Rect {
+    Rect {
+        x: 0.0,
+        y: 0.0,
+        width: PAGE_WIDTH,
+        height: PAGE_HEIGHT,
+    }
+}
+
+/// The page area without its padding, available to the keys of a direct access page. Geo
+/// profiles fill the whole page area, their root container has its own padding.
+#[must_use]
+pub fn content() -> Rect {
+    let padding = PAGE_PADDING * REM;
+    page().inset(padding, padding, padding, padding)
+}
+
+/// Boxes of the keys of a direct access page, filling the grid column by column. Rows share
+/// the page height, columns have a fixed width.
+#[must_use]
+pub fn grid(page: &DirectAccessPage) -> Vec<Rect> {
+    let area = content();
+    let rows = page.rows.max(1) as usize;
+    let gap = KEY_GAP * REM;
+    let width = KEY_WIDTH * REM;
+    let height = (area.height - gap * (rows - 1) as f64) / rows as f64;
+
+    (0..page.keys.len())
+        .map(|i| Rect {
+            x: area.x + (i / rows) as f64 * (width + gap),
+            y: area.y + (i % rows) as f64 * (height + gap),
+            width,
+            height,
+        })
+        .collect()
+}
+
+/// A node of a geo profile.
+#[derive(Debug, Clone, Copy)]
+pub enum GeoItem<'a> {
+    Container(&'a GeoContainer),
+    Button(&'a GeoButton),
+    Divider(&'a GeoDivider),
+}
+
+impl<'a> From<&'a GeoNode> for GeoItem<'a> {
+    fn from(node: &'a GeoNode) -> Self {
+        match node {
+            GeoNode::Container(container) => GeoItem::Container(container),
+            GeoNode::Button(button) => GeoItem::Button(button),
+            GeoNode::Divider(divider) => GeoItem::Divider(divider),
+        }
+    }
+}
+
+/// A laid out geo node and its children.
+#[derive(Debug)]
+pub struct GeoBox<'a> {
+    pub item: GeoItem<'a>,
+    pub rect: Rect,
+    pub children: Vec<GeoBox<'a>>,
+}
+
+impl<'a> GeoBox<'a> {
+    /// Calls `f` with the path, parent and box of this box and all boxes below it, in document
+    /// order. Paths name the node in the profile file, e.g. `children[2].children[0]`, and are
+    /// empty for the root.
+    pub fn visit(&self, f: &mut impl FnMut(&str, Option<&GeoBox<'a>>, &GeoBox<'a>)) {
+        self.visit_at("", None, f);
+    }
+
+    fn visit_at(
+        &self,
+        path: &str,
+        parent: Option<&GeoBox<'a>>,
+        f: &mut impl FnMut(&str, Option<&GeoBox<'a>>, &GeoBox<'a>),
+    ) {
+        f(path, parent, self);
+        for (i, child) in self.children.iter().enumerate() {
+            let path = if path.is_empty() {
+                format!("children[{i}]")
+            } else {
+                format!("{path}.children[{i}]")
+            };
+            child.visit_at(&path, Some(self), f);
+        }
+    }
+}
+
+/// Width and height a label needs to be displayed without clipping, including its padding.
+#[must_use]
+pub fn label_size(label: &Label) -> (f64, f64) {
+    let chars = label
+        .0
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default();
+    (
+        chars as f64 * CHAR_WIDTH + 2.0 * LABEL_PADDING,
+        label.0.len() as f64 * LINE_HEIGHT + 2.0 * LABEL_PADDING,
+    )
+}
+
+/// Lays out a geo profile, its root container filling the page area.
+#[must_use]
+pub fn geo(root: &GeoContainer) -> GeoBox<'_> {
+    let rect = page();
+    GeoBox {
+        item: GeoItem::Container(root),
+        rect,
+        children: children(root, rect),
+    }
+}
+
+/// The percentage of a container length such as `50%`, `None` for fixed or invalid lengths.
+#[must_use]
+pub fn percentage(value: &str) -> Option<f64> {
+    value.strip_suffix('%')?.parse().ok()
+}
+
+/// A container length, `None` if it is not set or invalid.
+fn length(value: Option<&str>, reference: f64) -> Option<f64> {
+    let value = value?;
+    if let Some(percent) = percentage(value) {
+        Some(percent / 100.0 * reference)
+    } else {
+        let rem = value.strip_suffix("rem")?;
+        rem.parse::<f64>().ok().map(|r| r * REM)
+    }
+}
+
+/// Paddings of a container in pixels: top, right, bottom, left.
+fn paddings(container: &GeoContainer) -> [f64; 4] {
+    let side = |value: Option<f64>| value.or(container.padding).unwrap_or_default() * REM;
+    [
+        side(container.padding_top),
+        side(container.padding_right),
+        side(container.padding_bottom),
+        side(container.padding_left),
+    ]
+}
+
+/// Width and height of a node given by its content, ignoring percentage lengths.
+fn content_size(item: GeoItem<'_>) -> (f64, f64) {
+    match item {
+        GeoItem::Button(button) => (button.size * REM, button.size * REM),
+        GeoItem::Divider(divider) => match divider.orientation {
+            DividerOrientation::Vertical => (divider.thickness, 0.0),
+            DividerOrientation::Horizontal => (0.0, divider.thickness),
+        },
+        GeoItem::Container(container) => {
+            let row = container.direction == FlexDirection::Row;
+            let gap = container.gap.unwrap_or_default() * REM;
+            let (mut main, mut cross) = (0.0, 0.0_f64);
+            for child in &container.children {
+                let (width, height) = content_size(child.into());
+                let (child_main, child_cross) = if row {
+                    (width, height)
+                } else {
+                    (height, width)
+                };
+                main += child_main;
+                cross = cross.max(child_cross);
+            }
+            main += gap * container.children.len().saturating_sub(1) as f64;
+
+            let [top, right, bottom, left] = paddings(container);
+            let (width, height) = if row {
+                (main + left + right, cross + top + bottom)
+            } else {
+                (cross + left + right, main + top + bottom)
+            };
+            // Percentages cannot be resolved without the parent, fixed lengths always apply.
+            let fixed = |value: Option<&str>| {
+                value
+                    .filter(|v| v.ends_with("rem"))
+                    .and_then(|v| length(Some(v), 0.0))
+            };
+            (
+                fixed(container.width.as_deref()).unwrap_or(width),
+                fixed(container.height.as_deref()).unwrap_or(height),
+            )
+        }
+    }
+}
+
+/// A flex item while laying out the children of a container, sizes along the main axis.
+struct Item {
+    basis: f64,
+    min: f64,
+    shrink: bool,
+    size: f64,
+    cross: f64,
+    /// Offset along the cross axis forced by the item, e.g. for oversized dividers.
+    cross_offset: Option<f64>,
+}
+
+/// Lays out the children of a container within its border box.
+fn children<'a>(container: &'a GeoContainer, rect: Rect) -> Vec<GeoBox<'a>> {
+    let [top, right, bottom, left] = paddings(container);
+    let area = rect.inset(top, right, bottom, left);
+    let row = container.direction == FlexDirection::Row;
+    let (main_length, cross_length) = if row {
+        (area.width, area.height)
+    } else {
+        (area.height, area.width)
+    };
+    let gap = container.gap.unwrap_or_default() * REM;
+    let align = container.align_items.as_deref().unwrap_or("stretch");
+
+    let mut items = container
+        .children
+        .iter()
+        .map(|child| {
+            let (width, height) = content_size(child.into());
+            let (content_main, content_cross) = if row {
+                (width, height)
+            } else {
+                (height, width)
+            };
+            match child {
+                GeoNode::Container(child) => {
+                    let (main, cross) = if row {
+                        (&child.width, &child.height)
+                    } else {
+                        (&child.height, &child.width)
+                    };
+                    let main = length(main.as_deref(), main_length);
+                    let cross = length(cross.as_deref(), cross_length);
+                    let basis = main.unwrap_or(content_main);
+                    Item {
+                        basis,
+                        min: main.map_or(content_main, |main| main.min(content_main)),
+                        shrink: true,
+                        size: basis,
+                        cross: cross.unwrap_or(if align == "stretch" {
+                            cross_length
+                        } else {
+                            content_cross
+                        }),
+                        cross_offset: None,
+                    }
+                }
+                GeoNode::Button(_) => Item {
+                    basis: content_main,
+                    min: content_main,
+                    shrink: false,
+                    size: content_main,
+                    cross: content_cross,
+                    cross_offset: None,
+                },
+                GeoNode::Divider(divider) => {
+                    // Dividers stretch along the cross axis, extended by their oversize.
+                    let oversize = divider.oversize.unwrap_or_default();
+                    let across = (divider.orientation == DividerOrientation::Vertical) == row;
+                    Item {
+                        basis: content_main,
+                        min: content_main,
+                        shrink: false,
+                        size: content_main,
+                        cross: if across {
+                            cross_length + 2.0 * oversize
+                        } else {
+                            content_cross
+                        },
+                        cross_offset: across.then_some(-oversize),
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let gaps = gap * items.len().saturating_sub(1) as f64;
+    shrink(&mut items, main_length - gaps);
+    let free = main_length - gaps - items.iter().map(|i| i.size).sum::<f64>();
+
+    let count = items.len() as f64;
+    let (mut position, spacing) = match container.justify_content.as_deref() {
+        Some("flex-end") => (free, 0.0),
+        Some("center") => (free / 2.0, 0.0),
+        Some("space-between") if free > 0.0 && items.len() > 1 => (0.0, free / (count - 1.0)),
+        Some("space-around") if free > 0.0 => (free / count / 2.0, free / count),
+        Some("space-evenly") if free > 0.0 => (free / (count + 1.0), free / (count + 1.0)),
+        Some("space-around" | "space-evenly") => (free / 2.0, 0.0),
+        _ => (0.0, 0.0),
+    };
+
+    let mut boxes = Vec::with_capacity(items.len());
+    for (child, item) in container.children.iter().zip(&items) {
+        let offset = item.cross_offset.unwrap_or(match align {
+            "center" => (cross_length - item.cross) / 2.0,
+            "flex-end" => cross_length - item.cross,
+            _ => 0.0,
+        });
+        let rect = if row {
+            Rect {
+                x: area.x + position,
+                y: area.y + offset,
+                width: item.size,
+                height: item.cross,
+            }
+        } else {
+            Rect {
+                x: area.x + offset,
+                y: area.y + position,
+                width: item.cross,
+                height: item.size,
+            }
+        };
+        position += item.size + gap + spacing;
+
+        boxes.push(GeoBox {
+            item: child.into(),
+            rect,
+            children: match child {
+                GeoNode::Container(container) => children(container, rect),
+                _ => Vec::new(),
+            },
+        });
+    }
+    boxes
+}
+
+/// Shrinks items in proportion to their basis until they fit the available length, but not
+/// below their minimum size. Items hitting their minimum are frozen and the rest is shrunk again.
+fn shrink(items: &mut [Item], available: f64) {
+    let mut frozen = items.iter().map(|i| !i.shrink).collect::<Vec<_>>();
+    loop {
+        let used = items
+            .iter()
+            .zip(&frozen)
+            .map(|(item, &frozen)| if frozen { item.size } else { item.basis })
+            .sum::<f64>();
+        let free = available - used;
+        let total = items
+            .iter()
+            .zip(&frozen)
+            .filter(|(_, frozen)| !**frozen)
+            .map(|(item, _)| item.basis)
+            .sum::<f64>();
+        if free >= 0.0 || total <= 0.0 {
+            for (item, _) in items
+                .iter_mut()
+                .zip(&frozen)
+                .filter(|(_, frozen)| !**frozen)
+            {
+                item.size = item.basis;
+            }
+            return;
+        }
+
+        let mut violated = false;
+        for (item, frozen) in items.iter_mut().zip(frozen.iter_mut()) {
+            if *frozen {
+                continue;
+            }
+            item.size = item.basis + free * item.basis / total;
+            if item.size < item.min {
+                item.size = item.min;
+                *frozen = true;
+                violated = true;
+            }
+        }
+        if !violated {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Boxes of the children of a geo root given as JSON.
+    fn layout(root: serde_json::Value) -> Vec<Rect> {
+        let root = serde_json::from_value::<GeoContainer>(root).unwrap();
+        geo(&root).children.iter().map(|child| child.rect).collect()
+    }
+
+    fn assert_rects(actual: &[Rect], expected: &[Rect]) {
+        let close = |a: &Rect, b: &Rect| {
+            [a.x - b.x, a.y - b.y, a.width - b.width, a.height - b.height]
+                .iter()
+                .all(|d| d.abs() < 1e-9)
+        };
+        assert!(
+            actual.len() == expected.len() && actual.iter().zip(expected).all(|(a, b)| close(a, b)),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn root_fills_page_inside_its_padding() {
+        let root = serde_json::from_value::<GeoContainer>(json!({
+            "direction": "row",
+            "padding": 1,
+            "children": [{ "label": ["A"], "size": 2 }]
+        }))
+        .unwrap();
+        let root = geo(&root);
+        assert_eq!(root.rect, page());
+        assert_rects(
+            &root.children.iter().map(|c| c.rect).collect::<Vec<_>>(),
+            &[rect(16.0, 16.0, 32.0, 32.0)],
+        );
+    }
+
+    #[test]
+    fn justify_content_distributes_free_space() {
+        // Two 160px buttons leave 580px of free space in the 900px row.
+        let cases = [
+            (None, [0.0, 160.0]),
+            (Some("flex-start"), [0.0, 160.0]),
+            (Some("flex-end"), [580.0, 740.0]),
+            (Some("center"), [290.0, 450.0]),
+            (Some("space-between"), [0.0, 740.0]),
+            (Some("space-around"), [145.0, 595.0]),
+            (
+                Some("space-evenly"),
+                [580.0 / 3.0, 580.0 / 3.0 * 2.0 + 160.0],
+            ),
+        ];
+        for (justify, [first, second]) in cases {
+            let rects = layout(json!({
+                "direction": "row",
+                "justify_content": justify,
+                "children": [
+                    { "label": ["A"], "size": 10 },
+                    { "label": ["B"], "size": 10 }
+                ]
+            }));
+            assert_rects(
+                &rects,
+                &[
+                    rect(first, 0.0, 160.0, 160.0),
+                    rect(second, 0.0, 160.0, 160.0),
+                ],
+            );
+        }
+    }
+
+    #[test]
+    fn align_items_stretches_or_centers_containers() {
+        let child = json!({
+            "direction": "col",
+            "children": [{ "label": ["A"], "size": 2 }]
+        });
+        let stretched = layout(json!({ "direction": "row", "children": [child] }));
+        assert_rects(&stretched, &[rect(0.0, 0.0, 32.0, 544.0)]);
+
+        let centered = layout(json!({
+            "direction": "row",
+            "align_items": "center",
+            "children": [child]
+        }));
+        assert_rects(&centered, &[rect(0.0, 256.0, 32.0, 32.0)]);
+    }
+
+    #[test]
+    fn lengths_resolve_percentages_against_the_parent_and_rem_to_pixels() {
+        let rects = layout(json!({
+            "direction": "row",
+            "children": [
+                { "direction": "row", "width": "25%", "height": "50%", "children": [] },
+                { "direction": "row", "width": "10rem", "height": "2rem", "children": [] }
+            ]
+        }));
+        assert_rects(
+            &rects,
+            &[rect(0.0, 0.0, 225.0, 272.0), rect(225.0, 0.0, 160.0, 32.0)],
+        );
+    }
+
+    #[test]
+    fn shrink_freezes_items_at_their_minimum() {
+        // Both containers want the whole row. The first cannot shrink below its 640px button, so
+        // it is frozen and the second absorbs the rest. The 80px button never shrinks.
+        let rects = layout(json!({
+            "direction": "row",
+            "children": [
+                {
+                    "direction": "row",
+                    "width": "100%",
+                    "children": [{ "label": ["A"], "size": 40 }]
+                },
+                { "direction": "row", "width": "100%", "children": [] },
+                { "label": ["B"], "size": 5 }
+            ]
+        }));
+        assert_rects(
+            &rects,
+            &[
+                rect(0.0, 0.0, 640.0, 544.0),
+                rect(640.0, 0.0, 180.0, 544.0),
+                rect(820.0, 0.0, 80.0, 80.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn dividers_extend_across_by_their_oversize() {
+        let rects = layout(json!({
+            "direction": "row",
+            "align_items": "center",
+            "children": [
+                { "label": ["A"], "size": 2 },
+                { "orientation": "vertical", "thickness": 2, "color": "#000", "oversize": 8 },
+                { "orientation": "horizontal", "thickness": 2, "color": "#000" }
+            ]
+        }));
+        assert_rects(
+            &rects,
+            &[
+                rect(0.0, 256.0, 32.0, 32.0),
+                rect(32.0, -8.0, 2.0, 560.0),
+                rect(34.0, 271.0, 0.0, 2.0),
+            ],
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use super::layout::{self, GeoBox, GeoItem, Rect};
+use super::{FONT_SIZE, LINE_HEIGHT, PAGE_HEIGHT, PAGE_WIDTH, escape};
+use crate::dataset::Dataset;
+use crate::profile::{self, DirectAccessPage, GeoContainer, GeoNode, Label, Profile};
+use console::style;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+use vacs_data_diagnostics::log;
+
+/// A page of a profile, rendered to its own file.
+struct Page<'a> {
+    /// File name without extension, derived from the position of the page in the profile.
+    name: String,
+    content: Content<'a>,
+}
+
+enum Content<'a> {
+    Grid(&'a DirectAccessPage),
+    Geo(&'a GeoContainer),
+}
+
+/// Lowercase file name segment of a label, e.g. `kar-mun-n` for `["KAR_MUN", "N"]`.
+fn slug(label: &Label) -> String {
+    let slug = label
+        .0
+        .join(" ")
+        .to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "page".to_string()
+    } else {
+        slug
+    }
+}
+
+/// All pages of a profile: tabs or the geo root, followed by the subpages they open. Names
+/// are built from the 1-based index and label of every tab, key or button on the way, so they
+/// only change if the page moves.
+fn pages(profile: &Profile) -> Vec<Page<'_>> {
+    fn subpages<'a>(parent: &str, page: &'a DirectAccessPage, pages: &mut Vec<Page<'a>>) {
+        for (i, key) in page.keys.iter().enumerate() {
+            if let Some(subpage) = &key.page {
+                let name = format!("{parent}.{:02}-{}", i + 1, slug(&key.label));
+                pages.push(Page {
+                    name: name.clone(),
+                    content: Content::Grid(subpage),
+                });
+                subpages(&name, subpage, pages);
+            }
+        }
+    }
+
+    fn buttons<'a>(container: &'a GeoContainer, count: &mut usize, pages: &mut Vec<Page<'a>>) {
+        for child in &container.children {
+            match child {
+                GeoNode::Container(container) => buttons(container, count, pages),
+                GeoNode::Button(button) => {
+                    *count += 1;
+                    if let Some(page) = &button.page {
+                        let name = format!("geo.{:02}-{}", count, slug(&button.label));
+                        pages.push(Page {
+                            name: name.clone(),
+                            content: Content::Grid(page),
+                        });
+                        subpages(&name, page, pages);
+                    }
+                }
+                GeoNode::Divider(_) => {}
+            }
+        }
+    }
+
+    let mut pages = Vec::new();
+    match profile {
+        Profile::Tabbed { tabs, .. } => {
+            for (i, tab) in tabs.iter().enumerate() {
+                let name = format!("{:02}-{}", i + 1, slug(&tab.label));
+                pages.push(Page {
+                    name: name.clone(),
+                    content: Content::Grid(&tab.page),
+                });
+                subpages(&name, &tab.page, &mut pages);
+            }
+        }
+        Profile::Geo { root, .. } => {
+            pages.push(Page {
+                name: "geo".to_string(),
+                content: Content::Geo(root),
+            });
+            buttons(root, &mut 0, &mut pages);
+        }
+    }
+    pages
+}
+
+/// Formats a coordinate with at most two decimals, so output does not depend on rounding noise.
+fn num(value: f64) -> String {
+    let value = (value * 100.0).round() / 100.0;
+    if value == 0.0 {
+        "0".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+enum KeyStyle {
+    Station,
+    Unknown,
+    Page,
+    Disabled,
+}
+
+fn key(out: &mut String, rect: Rect, label: &Label, key_style: &KeyStyle) {
+    let (fill, stroke, stroke_width, color) = match key_style {
+        KeyStyle::Station => ("#d1d5db", "#6b7280", 1.0, "#111111"),
+        KeyStyle::Unknown => ("#fecaca", "#dc2626", 3.0, "#111111"),
+        KeyStyle::Page => ("#a5e8ff", "#6b7280", 1.0, "#111111"),
+        KeyStyle::Disabled => ("#d1d5db", "#6b7280", 1.0, "#6b7280"),
+    };
+    let _ = writeln!(
+        out,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\" fill=\"{fill}\" stroke=\"{stroke}\" stroke-width=\"{stroke_width}\"/>",
+        num(rect.x),
+        num(rect.y),
+        num(rect.width),
+        num(rect.height)
+    );
+
+    let lines = &label.0;
+    let first =
+        rect.y + rect.height / 2.0 - (lines.len().saturating_sub(1) as f64) * LINE_HEIGHT / 2.0;
+    for (i, line) in lines.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\" fill=\"{color}\">{}</text>",
+            num(rect.x + rect.width / 2.0),
+            num(first + i as f64 * LINE_HEIGHT),
+            escape(line)
+        );
+    }
+}
+
+/// Renders pages as SVG, collecting keys referencing unknown stations.
+struct Renderer<'a> {
+    stations: &'a HashSet<String>,
+    unknown: Vec<String>,
+}
+
+impl Renderer<'_> {
+    fn grid(&mut self, out: &mut String, page: &DirectAccessPage) {
+        if page.client_page.is_some() {
+            let area = layout::content();
+            let _ = writeln!(
+                out,
+                "<text x=\"{}\" y=\"{}\" fill=\"#6b7280\">List of online clients</text>",
+                num(area.x + area.width / 2.0),
+                num(area.y + area.height / 2.0)
+            );
+            return;
+        }
+
+        for (k, rect) in page.keys.iter().zip(layout::grid(page)) {
+            let key_style = if k.page.is_some() {
+                KeyStyle::Page
+            } else if let Some(station) = &k.station_id {
+                let station = station.to_string();
+                if self.stations.contains(&station) {
+                    KeyStyle::Station
+                } else {
+                    if !self.unknown.contains(&station) {
+                        self.unknown.push(station);
+                    }
+                    KeyStyle::Unknown
+                }
+            } else {
+                KeyStyle::Disabled
+            };
+            key(out, rect, &k.label, &key_style);
+        }
+    }
+
+    fn geo(out: &mut String, node: &GeoBox<'_>) {
+        match node.item {
+            GeoItem::Container(_) => {}
+            GeoItem::Button(button) => {
+                let key_style = if button.page.is_some() {
+                    KeyStyle::Page
+                } else {
+                    KeyStyle::Disabled
+                };
+                key(out, node.rect, &button.label, &key_style);
+            }
+            GeoItem::Divider(divider) => {
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    num(node.rect.x),
+                    num(node.rect.y),
+                    num(node.rect.width),
+                    num(node.rect.height),
+                    escape(&divider.color)
+                );
+            }
+        }
+        for child in &node.children {
+            Self::geo(out, child);
+        }
+    }
+
+    fn page(&mut self, title: &str, page: &Page<'_>) -> String {
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"monospace\" font-size=\"{3}\" font-weight=\"bold\" text-anchor=\"middle\" dominant-baseline=\"central\">\n<title>{2}</title>\n<rect width=\"{0}\" height=\"{1}\" fill=\"#b5bac4\"/>\n",
+            num(PAGE_WIDTH),
+            num(PAGE_HEIGHT),
+            escape(title),
+            num(FONT_SIZE)
+        );
+        match page.content {
+            Content::Grid(grid) => self.grid(&mut out, grid),
+            Content::Geo(root) => Self::geo(&mut out, &layout::geo(root)),
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+/// Renders every page of a profile, or of all profiles of the dataset, to an SVG file in a
+/// directory per profile. The output only depends on the profile and the dataset's station IDs,
+/// so snapshots of two revisions can be diffed.
+pub fn export(input: &Path, profile: Option<&str>, output: &Path) -> crate::Result<()> {
+    let (dataset, profiles) = match profile {
+        Some(profile) => {
+            let (dataset, profile) = super::load(input, profile)?;
+            (dataset, vec![profile])
+        }
+        None => {
+            crate::check_input_dir(input)?;
+            let dataset = Dataset::load(input)?;
+            let mut profiles = Vec::new();
+            for fir in &dataset.firs {
+                profiles.extend(profile::load(fir)?.into_iter().map(|(_, p)| p));
+            }
+            (dataset, profiles)
+        }
+    };
+    let stations = super::station_ids(&dataset);
+
+    for profile in &profiles {
+        let dir = output.join(profile.id());
+        std::fs::create_dir_all(&dir).map_err(|source| crate::Error::Write {
+            path: dir.clone(),
+            source,
+        })?;
+
+        let mut renderer = Renderer {
+            stations: &stations,
+            unknown: Vec::new(),
+        };
+        let pages = pages(profile);
+        for page in &pages {
+            let title = format!("{} {}", profile.id(), page.name);
+            crate::write_output(
+                Some(&dir.join(format!("{}.svg", page.name))),
+                &renderer.page(&title, page),
+            )?;
+        }
+
+        let id = style(format!("`{}`", profile.id())).cyan();
+        for station in &renderer.unknown {
+            log::warn(format_args!(
+                "Profile {id} references unknown station {}",
+                style(format!("`{station}`")).cyan()
+            ));
+        }
+        log::info(format_args!(
+            "Rendered {} pages of profile {id} to {dir:?}",
+            pages.len()
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! Tabular reports on the coverage of a FIR, e.g. for reviewing sectorisation in spreadsheets.
+
+pub mod footprint;
+pub mod matrix;
+pub mod staffing;
+
+use crate::dataset::Dataset;
+use std::fmt;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Csv,
+}
+
+impl ReportFormat {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "markdown",
+            ReportFormat::Csv => "csv",
+        }
+    }
+
+    #[must_use]
+    pub const fn variants() -> &'static [&'static str] {
+        &["markdown", "csv"]
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "csv" => Ok(ReportFormat::Csv),
+            other => Err(format!(
+                "invalid report format '{other}'. expected one of: {}",
+                Self::variants().join(", ")
+            )),
+        }
+    }
+}
+
+/// A table of a report, rendered in any [`ReportFormat`].
+#[derive(Debug, Default)]
+pub struct Table {
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    #[must_use]
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.markdown(),
+            ReportFormat::Csv => self.csv(),
+        }
+    }
+
+    fn markdown(&self) -> String {
+        let cell = |value: &str| value.replace('|', "\\|");
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "| {} |",
+            self.header
+                .iter()
+                .map(|h| cell(h))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        );
+        let _ = writeln!(out, "|{}", " :-- |".repeat(self.header.len()));
+        for row in &self.rows {
+            let _ = writeln!(
+                out,
+                "| {} |",
+                row.iter().map(|c| cell(c)).collect::<Vec<_>>().join(" | ")
+            );
+        }
+        out
+    }
+
+    fn csv(&self) -> String {
+        let cell = |value: &str| {
+            if value.contains([',', '"', '\n']) {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            } else {
+                value.to_string()
+            }
+        };
+        let line = |values: &[String]| values.iter().map(|v| cell(v)).collect::<Vec<_>>().join(",");
+
+        let mut out = line(&self.header);
+        out.push('\n');
+        for row in &self.rows {
+            out.push_str(&line(row));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Renders several tables of a report in sequence, separated by an empty line.
+fn render(tables: &[Table], format: ReportFormat) -> String {
+    tables
+        .iter()
+        .map(|table| table.render(format))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Loads the dataset and looks up the FIR a report is generated for, returning the index of the
+/// FIR in [`Dataset::firs`].
+fn load(input: &Path, fir: &str) -> crate::Result<(Dataset, usize)> {
+    crate::check_input_dir(input)?;
+    let dataset = Dataset::load(input)?;
+    let Some(index) = dataset
+        .firs
+        .iter()
+        .position(|f| f.id.eq_ignore_ascii_case(fir))
+    else {
+        return Err(crate::Error::UnknownFir {
+            fir: fir.to_string(),
+            input: input.to_path_buf(),
+        });
+    };
+    Ok((dataset, index))
+}
@@ -0,0 +1,68 @@
+use super::{ReportFormat, Table};
+use crate::coverage::resolve_coverage;
+use std::collections::HashSet;
+use std::path::Path;
+use vacs_protocol::vatsim::PositionId;
+
+/// Lists for every position of a FIR the stations it covers if it is the only controller online,
+/// and the stations it covers if every position of the dataset is online.
+pub fn export(
+    input: &Path,
+    fir: &str,
+    format: ReportFormat,
+    output: Option<&Path>,
+) -> crate::Result<()> {
+    let (dataset, index) = super::load(input, fir)?;
+    let fir = &dataset.firs[index];
+
+    let station_map = dataset.station_map();
+    let existing = dataset.positions().map(|p| &p.id).collect::<HashSet<_>>();
+    let lists = dataset
+        .stations()
+        .map(|s| (&s.id, resolve_coverage(&station_map, &s.id)))
+        .collect::<Vec<_>>();
+
+    // With full staffing, every station is covered by the first position of its list that
+    // exists, referenced but undefined positions can never be online.
+    let full = lists
+        .iter()
+        .map(|(id, list)| (*id, list.iter().find(|p| existing.contains(p))))
+        .collect::<Vec<_>>();
+
+    let mut table = Table {
+        header: [
+            "Position",
+            "Facility",
+            "Alone",
+            "Stations alone",
+            "Full staffing",
+            "Stations with full staffing",
+        ]
+        .map(String::from)
+        .to_vec(),
+        rows: Vec::with_capacity(fir.positions.len()),
+    };
+    for position in &fir.positions {
+        let alone = lists
+            .iter()
+            .filter(|(_, list)| list.contains(&position.id))
+            .map(|(id, _)| id.to_string())
+            .collect::<Vec<_>>();
+        let staffed = full
+            .iter()
+            .filter(|(_, covering)| *covering == Some(&position.id as &PositionId))
+            .map(|(id, _)| id.to_string())
+            .collect::<Vec<_>>();
+
+        table.rows.push(vec![
+            position.id.to_string(),
+            position.facility_type.as_str().to_string(),
+            alone.len().to_string(),
+            alone.join(" "),
+            staffed.len().to_string(),
+            staffed.join(" "),
+        ]);
+    }
+
+    crate::write_output(output, &table.render(format))
+}
@@ -0,0 +1,64 @@
+use super::{ReportFormat, Table};
+use crate::coverage::resolve_coverage;
+use crate::dataset::Fir;
+use std::path::Path;
+use vacs_protocol::vatsim::PositionId;
+
+/// Builds a station × position matrix for a FIR. Each cell holds the rank of the position in
+/// the station's resolved coverage list, empty if the position cannot cover the station.
+pub fn export(
+    input: &Path,
+    fir: &str,
+    format: ReportFormat,
+    output: Option<&Path>,
+) -> crate::Result<()> {
+    let (dataset, index) = super::load(input, fir)?;
+    let fir = &dataset.firs[index];
+
+    let stations = dataset.station_map();
+    let lists = fir
+        .stations
+        .iter()
+        .map(|s| resolve_coverage(&stations, &s.id))
+        .collect::<Vec<_>>();
+    let columns = columns(fir, &lists);
+
+    let mut table = Table {
+        header: std::iter::once("Station".to_string())
+            .chain(columns.iter().cloned())
+            .collect(),
+        rows: Vec::with_capacity(fir.stations.len()),
+    };
+    for (station, list) in fir.stations.iter().zip(&lists) {
+        let mut row = vec![station.id.to_string()];
+        row.extend(columns.iter().map(|column| {
+            list.iter()
+                .position(|p| p.to_string() == *column)
+                .map(|rank| (rank + 1).to_string())
+                .unwrap_or_default()
+        }));
+        table.rows.push(row);
+    }
+
+    crate::write_output(output, &table.render(format))
+}
+
+/// Positions covering the FIR's stations: the FIR's own positions in dataset order, followed by
+/// positions of other FIRs inherited through `controlled_by` or `parent_id`, ordered by ID.
+fn columns(fir: &Fir, lists: &[Vec<PositionId>]) -> Vec<String> {
+    let mut columns = fir
+        .positions
+        .iter()
+        .map(|p| p.id.to_string())
+        .collect::<Vec<_>>();
+    let mut foreign = lists
+        .iter()
+        .flatten()
+        .map(ToString::to_string)
+        .filter(|id| !columns.contains(id))
+        .collect::<Vec<_>>();
+    foreign.sort();
+    foreign.dedup();
+    columns.extend(foreign);
+    columns
+}
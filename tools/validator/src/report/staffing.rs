@@ -0,0 +1,91 @@
+use super::{ReportFormat, Table};
+use crate::staffing::{MAX_SETS, Staffing};
+use console::style;
+use std::path::Path;
+use vacs_data_diagnostics::log;
+
+fn id(id: impl std::fmt::Display) -> String {
+    style(format!("`{id}`")).cyan().to_string()
+}
+
+/// Computes the smallest sets of positions covering every station of a FIR. The report lists
+/// the minimal sets, the stations only one position can cover, and the covering position of
+/// every station for each minimal set.
+pub fn export(
+    input: &Path,
+    fir: &str,
+    format: ReportFormat,
+    output: Option<&Path>,
+) -> crate::Result<()> {
+    let (dataset, index) = super::load(input, fir)?;
+    let fir = &dataset.firs[index];
+    let staffing = Staffing::analyze(&dataset, fir);
+
+    let size = staffing.sets.first().map(Vec::len).unwrap_or_default();
+    log::info(format_args!(
+        "Minimum staffing of FIR {}: {size} positions, {}{} minimal sets",
+        fir.id,
+        if staffing.truncated { "more than " } else { "" },
+        staffing.sets.len()
+    ));
+    if staffing.truncated {
+        log::info(format_args!("Only the first {MAX_SETS} sets are listed"));
+    }
+    for station in staffing.uncoverable() {
+        log::warn(format_args!(
+            "Station {} cannot be covered by any defined position",
+            id(station)
+        ));
+    }
+
+    let mut tables = vec![Table {
+        header: ["Set", "Positions"].map(String::from).to_vec(),
+        rows: staffing
+            .sets
+            .iter()
+            .enumerate()
+            .map(|(i, set)| {
+                vec![
+                    format!("Set {}", i + 1),
+                    set.iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ]
+            })
+            .collect(),
+    }];
+
+    let single = staffing
+        .single_candidate()
+        .map(|(station, position)| vec![station.to_string(), position.to_string()])
+        .collect::<Vec<_>>();
+    if !single.is_empty() {
+        tables.push(Table {
+            header: ["Station", "Only position"].map(String::from).to_vec(),
+            rows: single,
+        });
+    }
+
+    let mut table = Table {
+        header: ["Station", "Candidates"]
+            .into_iter()
+            .map(String::from)
+            .chain((1..=staffing.sets.len()).map(|i| format!("Set {i}")))
+            .collect(),
+        rows: Vec::with_capacity(staffing.stations.len()),
+    };
+    for (station, list) in &staffing.stations {
+        let mut row = vec![station.to_string(), list.len().to_string()];
+        row.extend(staffing.sets.iter().map(|set| {
+            list.iter()
+                .find(|p| set.contains(p))
+                .map(ToString::to_string)
+                .unwrap_or_default()
+        }));
+        table.rows.push(row);
+    }
+    tables.push(table);
+
+    crate::write_output(output, &super::render(&tables, format))
+}
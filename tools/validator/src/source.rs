@@ -0,0 +1,734 @@
+//! Resolves diagnostics to locations in the dataset files.
+//!
+//! The dataset types do not retain any position information, so files are parsed a second time
+//! into a minimal span-aware tree when a diagnostic needs to point at them.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use vacs_data_diagnostics::Location;
+
+/// A dataset file of a FIR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceFile {
+    Stations,
+    Positions,
+    Profile(String),
+}
+
+/// Kind of entity an ID refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityKind {
+    Station,
+    Position,
+    Profile,
+}
+
+/// What to point at inside a dataset file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Needle {
+    /// The file as a whole.
+    File,
+    /// The `id` field of the entity with the given ID.
+    Id(String),
+    /// The second `id` field with the given ID, falling back to the first one.
+    Duplicate(String),
+    /// A reference to the given ID in any field other than `id`, within the entity with the ID
+    /// `entity` if given and anywhere in the file otherwise.
+    Reference { id: String, entity: Option<String> },
+    /// The value of a field of the entity with the given ID, e.g. its `parent_id`.
+    Field { id: String, field: String },
+    /// The value at a path of keys and array indices from the root of the file, e.g.
+    /// `children[1].children[0]`. An empty path is the root itself.
+    Path(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lookup {
+    pub fir: String,
+    pub file: SourceFile,
+    pub needle: Needle,
+}
+
+impl Lookup {
+    #[must_use]
+    pub fn new(fir: impl Into<String>, file: SourceFile, needle: Needle) -> Self {
+        Self {
+            fir: fir.into(),
+            file,
+            needle,
+        }
+    }
+}
+
+/// A parsed dataset file, keeping its content for line and column calculations.
+#[derive(Debug)]
+pub struct ParsedFile {
+    pub path: PathBuf,
+    pub content: String,
+    root: Option<Node>,
+}
+
+impl ParsedFile {
+    fn parse(path: PathBuf) -> Option<Self> {
+        let content = std::fs::read_to_string(&path).ok()?;
+        let root = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml_tree(&content),
+            Some("json") => json::parse(&content),
+            _ => None,
+        };
+        Some(Self {
+            path,
+            content,
+            root,
+        })
+    }
+
+    /// Returns the byte span of the value matching the needle, the first string value in document
+    /// order for needles matching several.
+    #[must_use]
+    pub fn find(&self, needle: &Needle) -> Option<Range<usize>> {
+        let root = self.root.as_ref()?;
+        let mut found = Vec::new();
+        match needle {
+            Needle::File => return None,
+            Needle::Path(path) => return root.at(path)?.span(),
+            Needle::Id(id) | Needle::Duplicate(id) => {
+                root.collect(None, &mut |key, value, span| {
+                    if key == Some("id") && value == id {
+                        found.push(span);
+                    }
+                })
+            }
+            Needle::Reference { id, entity: None } => {
+                root.collect(None, &mut |key, value, span| {
+                    if key != Some("id") && value == id {
+                        found.push(span);
+                    }
+                });
+            }
+            Needle::Reference {
+                id,
+                entity: Some(entity),
+            } => root.tables(&mut |entries| {
+                if !is_entity(entries, entity) {
+                    return;
+                }
+                for (key, value) in entries.iter().filter(|(key, _)| key != "id") {
+                    value.collect(Some(key), &mut |key, value, span| {
+                        if key != Some("id") && value == id {
+                            found.push(span);
+                        }
+                    });
+                }
+            }),
+            Needle::Field { id, field } => root.tables(&mut |entries| {
+                let value = entries.iter().find(|(key, _)| key == field);
+                if let (true, Some((key, value))) = (is_entity(entries, id), value) {
+                    value.collect(Some(key), &mut |_, _, span| found.push(span));
+                }
+            }),
+        }
+        found.sort_by_key(|s| s.start);
+        match needle {
+            Needle::Duplicate(_) if found.len() > 1 => found.into_iter().nth(1),
+            _ => found.into_iter().next(),
+        }
+    }
+
+    /// Returns all values of `id` fields in document order.
+    #[must_use]
+    pub fn ids(&self) -> Vec<&str> {
+        let mut ids = Vec::new();
+        if let Some(root) = &self.root {
+            root.collect(None, &mut |key, value, span| {
+                if key == Some("id") {
+                    ids.push((span.start, value));
+                }
+            });
+        }
+        ids.sort_unstable_by_key(|(start, _)| *start);
+        ids.into_iter().map(|(_, id)| id).collect()
+    }
+
+    #[must_use]
+    pub fn location(&self, span: Range<usize>) -> Location {
+        let (line, column) = self.line_column(span.start);
+        let (end_line, end_column) = self.line_column(span.end.saturating_sub(1).max(span.start));
+        Location {
+            file: self.path.clone(),
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+
+    fn line_column(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.content.len());
+        let before = &self.content[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (line, before[line_start..].chars().count() + 1)
+    }
+}
+
+/// Cache of parsed dataset files below a dataset root.
+#[derive(Debug)]
+pub struct SourceMap {
+    root: PathBuf,
+    files: HashMap<PathBuf, Option<ParsedFile>>,
+}
+
+impl SourceMap {
+    #[must_use]
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            files: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn path(&self, fir: &str, file: &SourceFile) -> Option<PathBuf> {
+        let dir = self.root.join(fir);
+        let (dir, name) = match file {
+            SourceFile::Stations => (dir, "stations"),
+            SourceFile::Positions => (dir, "positions"),
+            SourceFile::Profile(id) => (dir.join("profiles"), id.as_str()),
+        };
+
+        let candidates = [
+            dir.join(format!("{name}.toml")),
+            dir.join(format!("{name}.json")),
+            dir.join(name),
+        ];
+        if let Some(path) = candidates.into_iter().find(|p| p.is_file()) {
+            return Some(path);
+        }
+
+        // Profile files are usually, but not necessarily, named after the profile ID.
+        std::fs::read_dir(&dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .find(|p| {
+                p.file_stem()
+                    .is_some_and(|s| s.to_string_lossy().eq_ignore_ascii_case(name))
+            })
+    }
+
+    pub fn file(&mut self, path: &Path) -> Option<&ParsedFile> {
+        self.files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| ParsedFile::parse(path.to_path_buf()))
+            .as_ref()
+    }
+
+    /// Returns the names of all FIR directories, sorted.
+    #[must_use]
+    pub fn firs(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        let mut firs = entries
+            .filter_map(Result::ok)
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        firs.sort();
+        firs
+    }
+
+    /// Returns the IDs of all entities of a kind defined in a FIR directory.
+    pub fn fir_ids(&mut self, fir: &str, kind: EntityKind) -> Vec<String> {
+        let paths = match kind {
+            EntityKind::Station => self.path(fir, &SourceFile::Stations).into_iter().collect(),
+            EntityKind::Position => self.path(fir, &SourceFile::Positions).into_iter().collect(),
+            EntityKind::Profile => {
+                let mut profiles = std::fs::read_dir(self.root.join(fir).join("profiles"))
+                    .map(|entries| {
+                        entries
+                            .filter_map(Result::ok)
+                            .map(|e| e.path())
+                            .filter(|p| p.is_file())
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                profiles.sort();
+                profiles
+            }
+        };
+
+        let mut ids = Vec::new();
+        for path in paths {
+            let Some(file) = self.file(&path) else {
+                continue;
+            };
+            let file_ids = file.ids();
+            match kind {
+                // Only the top-level ID identifies a profile.
+                EntityKind::Profile => ids.extend(file_ids.first().map(|id| id.to_string())),
+                _ => ids.extend(file_ids.into_iter().map(str::to_string)),
+            }
+        }
+        ids
+    }
+
+    /// Returns the IDs of all entities of a kind defined anywhere in the dataset.
+    pub fn ids(&mut self, kind: EntityKind) -> Vec<String> {
+        self.firs()
+            .iter()
+            .flat_map(|fir| self.fir_ids(fir, kind))
+            .collect()
+    }
+
+    pub fn locate(&mut self, lookup: &Lookup) -> Option<Location> {
+        let path = self.path(&lookup.fir, &lookup.file)?;
+        let Some(file) = self.file(&path) else {
+            return Some(Location::file(path));
+        };
+        Some(match file.find(&lookup.needle) {
+            Some(span) => file.location(span),
+            None => Location::file(path),
+        })
+    }
+
+    /// Derives the dataset file from a context chain as reported by the network loader, e.g.
+    /// `["EF", "profiles", "EFIN"]`, and locates the needle in it. References are only searched
+    /// within the entity named by the context, if any.
+    pub fn locate_context(&mut self, context: &[String], needle: Needle) -> Option<Location> {
+        let parts = context
+            .iter()
+            .flat_map(|c| c.split(['/', '\\', '>']))
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>();
+
+        let fir_idx = parts.iter().position(|p| self.root.join(p).is_dir())?;
+        let fir = parts[fir_idx];
+        let rest = &parts[fir_idx + 1..];
+
+        let mut file = None;
+        let mut entity = None;
+        for (idx, part) in rest.iter().enumerate() {
+            let stem = part.split('.').next().unwrap_or(part);
+            match stem.to_ascii_lowercase().as_str() {
+                "stations" => file = Some(SourceFile::Stations),
+                "positions" => file = Some(SourceFile::Positions),
+                "profiles" => {
+                    if let Some(profile) = rest.get(idx + 1) {
+                        let profile = profile.rsplit_once('.').map_or(*profile, |(s, _)| s);
+                        file = Some(SourceFile::Profile(profile.to_string()));
+                    }
+                    break;
+                }
+                _ if file.is_some() => entity = Some(part.to_string()),
+                _ => {}
+            }
+        }
+
+        let needle = match (needle, entity) {
+            (Needle::File, Some(entity)) => Needle::Id(entity),
+            (Needle::Reference { id, entity: None }, entity) => Needle::Reference { id, entity },
+            (needle, _) => needle,
+        };
+        self.locate(&Lookup::new(fir, file?, needle))
+    }
+}
+
+/// Returns whether the entries of a table are those of the entity with the given ID.
+fn is_entity(entries: &[(String, Node)], id: &str) -> bool {
+    entries
+        .iter()
+        .any(|(key, value)| key == "id" && matches!(value, Node::String(value, _) if value == id))
+}
+
+/// Minimal span-aware document tree, only keeping what is needed to find string values and the
+/// tables and arrays containing them.
+#[derive(Debug)]
+enum Node {
+    String(String, Range<usize>),
+    Array(Vec<Node>, Range<usize>),
+    Table(Vec<(String, Node)>, Range<usize>),
+    Other,
+}
+
+impl Node {
+    /// Calls `f` with the closest key, value and span of every string in the tree.
+    fn collect<'a>(
+        &'a self,
+        key: Option<&'a str>,
+        f: &mut impl FnMut(Option<&'a str>, &'a str, Range<usize>),
+    ) {
+        match self {
+            Node::String(value, span) => f(key, value, span.clone()),
+            Node::Array(items, _) => items.iter().for_each(|i| i.collect(key, f)),
+            Node::Table(entries, _) => entries.iter().for_each(|(k, v)| v.collect(Some(k), f)),
+            Node::Other => {}
+        }
+    }
+
+    /// Calls `f` with the entries of every table in the tree.
+    fn tables(&self, f: &mut impl FnMut(&[(String, Node)])) {
+        match self {
+            Node::Array(items, _) => items.iter().for_each(|i| i.tables(f)),
+            Node::Table(entries, _) => {
+                f(entries);
+                entries.iter().for_each(|(_, v)| v.tables(f));
+            }
+            Node::String(..) | Node::Other => {}
+        }
+    }
+
+    /// Returns the node at a path like `children[1].children[0]`, see [`Needle::Path`].
+    fn at(&self, path: &str) -> Option<&Node> {
+        let mut node = self;
+        for segment in path.split('.').filter(|s| !s.is_empty()) {
+            let (key, indices) = segment
+                .find('[')
+                .map_or((segment, ""), |i| segment.split_at(i));
+            if !key.is_empty() {
+                let Node::Table(entries, _) = node else {
+                    return None;
+                };
+                node = entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)?;
+            }
+            for index in indices.split('[').filter(|i| !i.is_empty()) {
+                let index = index.strip_suffix(']')?.parse::<usize>().ok()?;
+                let Node::Array(items, _) = node else {
+                    return None;
+                };
+                node = items.get(index)?;
+            }
+        }
+        Some(node)
+    }
+
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Node::String(_, span) | Node::Array(_, span) | Node::Table(_, span) => {
+                Some(span.clone())
+            }
+            Node::Other => None,
+        }
+    }
+}
+
+fn toml_tree(content: &str) -> Option<Node> {
+    use toml::Spanned;
+    use toml::de::{DeTable, DeValue};
+
+    fn convert(value: &Spanned<DeValue<'_>>) -> Node {
+        match value.get_ref() {
+            DeValue::String(s) => Node::String(s.to_string(), value.span()),
+            DeValue::Array(items) => Node::Array(items.iter().map(convert).collect(), value.span()),
+            DeValue::Table(table) => Node::Table(
+                table
+                    .iter()
+                    .map(|(k, v)| (k.get_ref().to_string(), convert(v)))
+                    .collect(),
+                value.span(),
+            ),
+            _ => Node::Other,
+        }
+    }
+
+    let table = DeTable::parse(content).ok()?;
+    Some(Node::Table(
+        table
+            .get_ref()
+            .iter()
+            .map(|(k, v)| (k.get_ref().to_string(), convert(v)))
+            .collect(),
+        table.span(),
+    ))
+}
+
+/// Small JSON parser recording the byte span of every string value.
+mod json {
+    use super::Node;
+
+    pub(super) fn parse(content: &str) -> Option<Node> {
+        let mut parser = Parser {
+            bytes: content.as_bytes(),
+            pos: 0,
+        };
+        let node = parser.value()?;
+        parser.whitespace();
+        (parser.pos == parser.bytes.len()).then_some(node)
+    }
+
+    struct Parser<'a> {
+        bytes: &'a [u8],
+        pos: usize,
+    }
+
+    impl Parser<'_> {
+        fn whitespace(&mut self) {
+            while self
+                .bytes
+                .get(self.pos)
+                .is_some_and(|b| b.is_ascii_whitespace())
+            {
+                self.pos += 1;
+            }
+        }
+
+        fn eat(&mut self, byte: u8) -> bool {
+            self.whitespace();
+            if self.bytes.get(self.pos) == Some(&byte) {
+                self.pos += 1;
+                true
+            } else {
+                false
+            }
+        }
+
+        fn value(&mut self) -> Option<Node> {
+            self.whitespace();
+            match self.bytes.get(self.pos)? {
+                b'{' => self.object(),
+                b'[' => self.array(),
+                b'"' => {
+                    let start = self.pos;
+                    let value = self.string()?;
+                    Some(Node::String(value, start..self.pos))
+                }
+                _ => {
+                    let start = self.pos;
+                    while self.bytes.get(self.pos).is_some_and(|b| {
+                        !matches!(b, b',' | b']' | b'}') && !b.is_ascii_whitespace()
+                    }) {
+                        self.pos += 1;
+                    }
+                    (self.pos > start).then_some(Node::Other)
+                }
+            }
+        }
+
+        fn object(&mut self) -> Option<Node> {
+            let start = self.pos;
+            self.pos += 1;
+            let mut entries = Vec::new();
+            if self.eat(b'}') {
+                return Some(Node::Table(entries, start..self.pos));
+            }
+            loop {
+                self.whitespace();
+                let key = self.string()?;
+                if !self.eat(b':') {
+                    return None;
+                }
+                entries.push((key, self.value()?));
+                if self.eat(b'}') {
+                    return Some(Node::Table(entries, start..self.pos));
+                }
+                if !self.eat(b',') {
+                    return None;
+                }
+            }
+        }
+
+        fn array(&mut self) -> Option<Node> {
+            let start = self.pos;
+            self.pos += 1;
+            let mut items = Vec::new();
+            if self.eat(b']') {
+                return Some(Node::Array(items, start..self.pos));
+            }
+            loop {
+                items.push(self.value()?);
+                if self.eat(b']') {
+                    return Some(Node::Array(items, start..self.pos));
+                }
+                if !self.eat(b',') {
+                    return None;
+                }
+            }
+        }
+
+        fn string(&mut self) -> Option<String> {
+            if self.bytes.get(self.pos) != Some(&b'"') {
+                return None;
+            }
+            let start = self.pos;
+            self.pos += 1;
+            loop {
+                match self.bytes.get(self.pos)? {
+                    b'\\' => self.pos += 2,
+                    b'"' => break,
+                    _ => self.pos += 1,
+                }
+            }
+            self.pos += 1;
+            let raw = std::str::from_utf8(&self.bytes[start..self.pos]).ok()?;
+            serde_json::from_str(raw).ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_file(content: &str) -> ParsedFile {
+        ParsedFile {
+            path: PathBuf::from("profile.json"),
+            content: content.to_string(),
+            root: json::parse(content),
+        }
+    }
+
+    fn strings(content: &str) -> Vec<(Option<String>, String)> {
+        let mut strings = Vec::new();
+        json::parse(content)
+            .expect("valid JSON")
+            .collect(None, &mut |key, value, _| {
+                strings.push((key.map(str::to_string), value.to_string()));
+            });
+        strings
+    }
+
+    #[test]
+    fn json_unescapes_strings() {
+        let content = r#"{"id": "A\"B", "label": "\u00e4\u20ac\n"}"#;
+        assert_eq!(
+            strings(content),
+            [
+                (Some("id".to_string()), "A\"B".to_string()),
+                (Some("label".to_string()), "ä€\n".to_string()),
+            ]
+        );
+
+        // The span covers the raw string including its quotes and escapes.
+        let file = json_file(content);
+        let span = file.find(&Needle::Id("A\"B".to_string())).unwrap();
+        assert_eq!(&content[span], r#""A\"B""#);
+    }
+
+    #[test]
+    fn json_parses_nested_arrays_and_objects() {
+        let content = r#"{
+  "id": "LOVV",
+  "children": [
+    { "label": ["KAR", "N"], "children": [[], [{ "id": "inner" }]] },
+    { "page": { "keys": [{ "station_id": "LOWW_APP" }] }, "size": 6.25, "open": true }
+  ]
+}"#;
+        let file = json_file(content);
+        assert_eq!(file.ids(), ["LOVV", "inner"]);
+
+        let span = file
+            .find(&Needle::Reference {
+                id: "LOWW_APP".to_string(),
+                entity: None,
+            })
+            .unwrap();
+        assert_eq!(&content[span.clone()], r#""LOWW_APP""#);
+        let location = file.location(span);
+        assert_eq!((location.line, location.column), (5, 42));
+        assert_eq!((location.end_line, location.end_column), (5, 51));
+
+        assert!(
+            strings(content).contains(&(Some("label".to_string()), "N".to_string())),
+            "array items inherit the key of their array"
+        );
+    }
+
+    #[test]
+    fn json_columns_count_characters() {
+        let content = "{\n  \"label\": \"Größe ✈\", \"id\": \"LOWW\"\n}";
+        let file = json_file(content);
+        let span = file.find(&Needle::Id("LOWW".to_string())).unwrap();
+        let location = file.location(span);
+        assert_eq!((location.line, location.column), (2, 29));
+        assert_eq!((location.end_line, location.end_column), (2, 34));
+    }
+
+    #[test]
+    fn reference_is_limited_to_its_entity() {
+        let content = r#"[[stations]]
+id = "EFIN_FMP"
+controlled_by = ["EFIN_X_CTR"]
+
+[[stations]]
+id = "EFIN_A"
+parent_id = "EFIN_FMP"
+controlled_by = ["EFIN_A_CTR", "EFIN_X_CTR"]
+"#;
+        let file = ParsedFile {
+            path: PathBuf::from("stations.toml"),
+            content: content.to_string(),
+            root: toml_tree(content),
+        };
+        let line = |entity: Option<&str>| {
+            let span = file.find(&Needle::Reference {
+                id: "EFIN_X_CTR".to_string(),
+                entity: entity.map(str::to_string),
+            })?;
+            Some(file.location(span).line)
+        };
+        assert_eq!(line(Some("EFIN_A")), Some(8));
+        assert_eq!(line(Some("EFIN_FMP")), Some(3));
+        assert_eq!(line(None), Some(3));
+        assert_eq!(line(Some("EFIN_B")), None);
+    }
+
+    #[test]
+    fn path_locates_nested_nodes() {
+        let content = r#"{
+  "id": "LOVV",
+  "children": [
+    { "label": ["KAR"] },
+    { "children": [{ "label": ["MUN", "N"] }] }
+  ]
+}"#;
+        let file = json_file(content);
+        let find = |path: &str| {
+            file.find(&Needle::Path(path.to_string()))
+                .map(|span| &content[span])
+        };
+        assert_eq!(find("children[0]"), Some(r#"{ "label": ["KAR"] }"#));
+        assert_eq!(
+            find("children[1].children[0]"),
+            Some(r#"{ "label": ["MUN", "N"] }"#)
+        );
+        assert_eq!(find("children[1].children[0].label[1]"), Some(r#""N""#));
+        assert_eq!(find(""), Some(content));
+        assert_eq!(find("children[2]"), None);
+        assert_eq!(find("children[0].children[0]"), None);
+        assert_eq!(find("id[0]"), None);
+    }
+
+    #[test]
+    fn toml_path_locates_nested_nodes() {
+        let content = "id = \"LOVV\"\nchildren = [{ label = [\"KAR\"] }, { label = [\"MUN\"] }]\n";
+        let file = ParsedFile {
+            path: PathBuf::from("profile.toml"),
+            content: content.to_string(),
+            root: toml_tree(content),
+        };
+        let span = file.find(&Needle::Path("children[1]".to_string())).unwrap();
+        assert_eq!(&content[span], r#"{ label = ["MUN"] }"#);
+    }
+
+    #[test]
+    fn json_rejects_malformed_input() {
+        for content in [
+            "",
+            "{",
+            r#"{"id": }"#,
+            r#"{"id" "LOWW"}"#,
+            r#"{"id": "LOWW",}"#,
+            r#"["a",]"#,
+            r#"["a" "b"]"#,
+            r#"{id: "LOWW"}"#,
+            r#"{"id": "LOWW"#,
+            r#"{"id": "\x"}"#,
+            r#"{"id": "LOWW"} trailing"#,
+        ] {
+            assert!(json::parse(content).is_none(), "accepted {content:?}");
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use crate::coverage::resolve_coverage;
+use crate::dataset::{Dataset, Fir};
+use std::collections::{HashMap, HashSet};
+use vacs_protocol::vatsim::{PositionId, StationId};
+
+/// Maximum number of minimal sets collected. FIRs with many interchangeable positions can have
+/// thousands of them, which is of no use for planning a roster.
+pub const MAX_SETS: usize = 10;
+
+/// Minimum staffing of a FIR: the smallest sets of positions covering every one of its stations
+/// through the resolved `controlled_by` lists.
+#[derive(Debug)]
+pub struct Staffing<'a> {
+    /// Every station of the FIR with the defined positions able to cover it, in coverage order.
+    pub stations: Vec<(&'a StationId, Vec<PositionId>)>,
+    /// Smallest sets of positions covering every coverable station, at most [`MAX_SETS`]. The
+    /// FIR's own positions come first in each set, in dataset order.
+    pub sets: Vec<Vec<PositionId>>,
+    /// Whether more minimal sets exist than listed in `sets`.
+    pub truncated: bool,
+}
+
+impl<'a> Staffing<'a> {
+    #[must_use]
+    pub fn analyze(dataset: &'a Dataset, fir: &'a Fir) -> Self {
+        let station_map = dataset.station_map();
+        let defined = dataset.positions().map(|p| &p.id).collect::<HashSet<_>>();
+        let stations = fir
+            .stations
+            .iter()
+            .map(|s| {
+                let mut list = resolve_coverage(&station_map, &s.id);
+                // Referenced but undefined positions can never be online.
+                list.retain(|p| defined.contains(p));
+                (&s.id, list)
+            })
+            .collect::<Vec<_>>();
+
+        let mut positions = fir
+            .positions
+            .iter()
+            .map(|p| p.id.clone())
+            .collect::<Vec<_>>();
+        let mut foreign = stations
+            .iter()
+            .flat_map(|(_, list)| list)
+            .filter(|p| !positions.contains(p))
+            .cloned()
+            .collect::<Vec<_>>();
+        foreign.sort();
+        foreign.dedup();
+        positions.extend(foreign);
+        let index = positions
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p, i))
+            .collect::<HashMap<_, _>>();
+
+        // Only coverable stations are part of the search, each one a bit in the position masks.
+        let coverable = stations
+            .iter()
+            .filter(|(_, list)| !list.is_empty())
+            .collect::<Vec<_>>();
+        let words = coverable.len().div_ceil(64);
+        let mut covers = vec![vec![0u64; words]; positions.len()];
+        let mut candidates = Vec::with_capacity(coverable.len());
+        for (bit, (_, list)) in coverable.iter().enumerate() {
+            let list = list.iter().map(|p| index[p]).collect::<Vec<_>>();
+            for &position in &list {
+                covers[position][bit / 64] |= 1 << (bit % 64);
+            }
+            candidates.push(list);
+        }
+
+        let mut uncovered = vec![u64::MAX; words];
+        if coverable.len() % 64 != 0 {
+            uncovered[words - 1] = (1 << (coverable.len() % 64)) - 1;
+        }
+
+        let mut search = Search {
+            covers: &covers,
+            candidates: &candidates,
+            sets: Vec::new(),
+        };
+        // Iterative deepening, so the first budget yielding any set is the minimum.
+        for budget in 0..=positions.len() {
+            search.run(
+                &uncovered,
+                &mut Vec::new(),
+                &mut vec![false; positions.len()],
+                budget,
+            );
+            if !search.sets.is_empty() {
+                break;
+            }
+        }
+
+        let truncated = search.sets.len() > MAX_SETS;
+        let sets = search
+            .sets
+            .into_iter()
+            .take(MAX_SETS)
+            .map(|mut set| {
+                set.sort_unstable();
+                set.into_iter().map(|i| positions[i].clone()).collect()
+            })
+            .collect();
+
+        Self {
+            stations,
+            sets,
+            truncated,
+        }
+    }
+
+    /// Stations that can only ever be covered by one specific position, which is therefore part
+    /// of every minimal set.
+    pub fn single_candidate(&self) -> impl Iterator<Item = (&StationId, &PositionId)> {
+        self.stations
+            .iter()
+            .filter_map(|(station, list)| match list.as_slice() {
+                [position] => Some((*station, position)),
+                _ => None,
+            })
+    }
+
+    /// Stations no defined position can cover.
+    pub fn uncoverable(&self) -> impl Iterator<Item = &StationId> {
+        self.stations
+            .iter()
+            .filter(|(_, list)| list.is_empty())
+            .map(|(station, _)| *station)
+    }
+}
+
+/// Exhaustive search for set covers of a fixed size.
+struct Search<'s> {
+    /// Stations covered by each position, as bit mask.
+    covers: &'s [Vec<u64>],
+    /// Positions able to cover each station, in coverage order.
+    candidates: &'s [Vec<usize>],
+    sets: Vec<Vec<usize>>,
+}
+
+impl Search<'_> {
+    /// Collects every set extending `chosen` by at most `budget` positions that covers all
+    /// `uncovered` stations without using `excluded` positions.
+    ///
+    /// Branches on the station with the fewest remaining candidates. A candidate is excluded
+    /// from all branches after its own, so every set is found exactly once.
+    fn run(
+        &mut self,
+        uncovered: &[u64],
+        chosen: &mut Vec<usize>,
+        excluded: &mut [bool],
+        budget: usize,
+    ) {
+        if self.sets.len() > MAX_SETS {
+            return;
+        }
+
+        let mut next: Option<(usize, usize)> = None;
+        for station in bits(uncovered) {
+            let count = self.candidates[station]
+                .iter()
+                .filter(|&&p| !excluded[p])
+                .count();
+            if next.is_none_or(|(_, fewest)| count < fewest) {
+                next = Some((station, count));
+            }
+        }
+        let Some((station, _)) = next else {
+            self.sets.push(chosen.clone());
+            return;
+        };
+        if budget == 0 {
+            return;
+        }
+
+        // Prune branches that cannot cover the remaining stations even with the widest positions.
+        let remaining = count(uncovered);
+        let widest = (0..self.covers.len())
+            .filter(|&p| !excluded[p])
+            .map(|p| count_common(uncovered, &self.covers[p]))
+            .max()
+            .unwrap_or_default();
+        if remaining > widest * budget {
+            return;
+        }
+
+        let options = self.candidates[station]
+            .iter()
+            .copied()
+            .filter(|&p| !excluded[p])
+            .collect::<Vec<_>>();
+        for &position in &options {
+            let rest = uncovered
+                .iter()
+                .zip(&self.covers[position])
+                .map(|(u, c)| u & !c)
+                .collect::<Vec<_>>();
+            chosen.push(position);
+            self.run(&rest, chosen, excluded, budget - 1);
+            chosen.pop();
+            excluded[position] = true;
+        }
+        for &position in &options {
+            excluded[position] = false;
+        }
+    }
+}
+
+fn bits(mask: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    mask.iter().enumerate().flat_map(|(word, &bits)| {
+        (0..64)
+            .filter(move |bit| bits & (1 << bit) != 0)
+            .map(move |bit| word * 64 + bit)
+    })
+}
+
+fn count(mask: &[u64]) -> usize {
+    mask.iter().map(|w| w.count_ones() as usize).sum()
+}
+
+fn count_common(a: &[u64], b: &[u64]) -> usize {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a & b).count_ones() as usize)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A dataset with a single FIR defining `positions`, and `stations` with their
+    /// `controlled_by` lists.
+    fn dataset(positions: &[&str], stations: &[(&str, &[&str])]) -> Dataset {
+        Dataset {
+            firs: vec![Fir {
+                id: "LO".to_string(),
+                path: "LO".into(),
+                stations: stations
+                    .iter()
+                    .map(|(id, controlled_by)| {
+                        toml::from_str(&format!(
+                            "id = \"{id}\"\ncontrolled_by = {controlled_by:?}"
+                        ))
+                        .unwrap()
+                    })
+                    .collect(),
+                positions: positions
+                    .iter()
+                    .map(|id| {
+                        toml::from_str(&format!(
+                            "id = \"{id}\"\nprefixes = [\"LOVV\"]\nfrequency = \"132.950\"\nfacility_type = \"CTR\""
+                        ))
+                        .unwrap()
+                    })
+                    .collect(),
+            }],
+        }
+    }
+
+    fn sets(staffing: &Staffing<'_>) -> Vec<Vec<String>> {
+        staffing
+            .sets
+            .iter()
+            .map(|set| set.iter().map(ToString::to_string).collect())
+            .collect()
+    }
+
+    #[test]
+    fn finds_the_minimum_size() {
+        let dataset = dataset(
+            &["P1", "P2", "P3"],
+            &[("S1", &["P1", "P3"]), ("S2", &["P2", "P3"])],
+        );
+        let staffing = Staffing::analyze(&dataset, &dataset.firs[0]);
+        assert_eq!(sets(&staffing), [["P3"]]);
+        assert!(!staffing.truncated);
+    }
+
+    #[test]
+    fn finds_every_set_exactly_once() {
+        let dataset = dataset(
+            &["P1", "P2", "P3", "P4"],
+            &[
+                ("S1", &["P1", "P2"]),
+                ("S2", &["P3", "P4"]),
+                ("S3", &["P2", "P4"]),
+            ],
+        );
+        let staffing = Staffing::analyze(&dataset, &dataset.firs[0]);
+        let mut found = sets(&staffing);
+        found.sort();
+        assert_eq!(found, [["P1", "P4"], ["P2", "P3"], ["P2", "P4"]]);
+        assert!(!staffing.truncated);
+    }
+
+    #[test]
+    fn truncates_at_max_sets() {
+        let dataset = dataset(
+            &["P1", "P2", "P3", "P4", "P5", "P6", "P7", "P8"],
+            &[
+                ("S1", &["P1", "P2", "P3", "P4"]),
+                ("S2", &["P5", "P6", "P7", "P8"]),
+            ],
+        );
+        let staffing = Staffing::analyze(&dataset, &dataset.firs[0]);
+        let found = sets(&staffing);
+        assert_eq!(found.len(), MAX_SETS);
+        assert!(found.iter().all(|set| set.len() == 2));
+        assert!(staffing.truncated);
+    }
+
+    #[test]
+    fn skips_uncoverable_stations() {
+        let dataset = dataset(
+            &["P1"],
+            &[("S1", &["P1"]), ("S2", &[]), ("S3", &["UNDEFINED"])],
+        );
+        let staffing = Staffing::analyze(&dataset, &dataset.firs[0]);
+        assert_eq!(sets(&staffing), [["P1"]]);
+        assert_eq!(
+            staffing
+                .uncoverable()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["S2", "S3"]
+        );
+        assert_eq!(
+            staffing
+                .single_candidate()
+                .map(|(station, position)| (station.to_string(), position.to_string()))
+                .collect::<Vec<_>>(),
+            [("S1".to_string(), "P1".to_string())]
+        );
+    }
+
+    #[test]
+    fn needs_no_positions_without_stations() {
+        let dataset = dataset(&["P1"], &[]);
+        let staffing = Staffing::analyze(&dataset, &dataset.firs[0]);
+        assert_eq!(sets(&staffing), [Vec::<String>::new()]);
+        assert!(!staffing.truncated);
+    }
+}
@@ -0,0 +1,67 @@
+//! "Did you mean" suggestions for references to IDs that do not exist.
+
+/// Maximum number of suggestions returned.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Normalizes an ID the way typos usually differ, e.g. `ESOS 6` and `ESOS__6` both become
+/// `ESOS_6`.
+fn normalize(id: &str) -> String {
+    let mut normalized = id.trim().to_ascii_uppercase().replace([' ', '-'], "_");
+    while normalized.contains("__") {
+        normalized = normalized.replace("__", "_");
+    }
+    normalized
+}
+
+/// Returns how far a candidate is from the missing ID, or `None` if it is not similar enough to
+/// be suggested. Lower is better.
+fn score(missing: &str, candidate: &str) -> Option<usize> {
+    if missing == candidate {
+        return Some(0);
+    }
+
+    // Truncated or extended IDs, e.g. `EFIN_V` vs. `EFIN_V_CTR`.
+    let (short, long) = if missing.len() < candidate.len() {
+        (missing, candidate)
+    } else {
+        (candidate, missing)
+    };
+    if long
+        .strip_prefix(short)
+        .is_some_and(|rest| rest.starts_with('_'))
+    {
+        return Some(1);
+    }
+
+    let distance = strsim::levenshtein(missing, candidate);
+    let threshold = (missing.chars().count() / 3).max(2);
+    (distance <= threshold).then_some(distance + 1)
+}
+
+/// Returns up to three existing IDs closest to the missing one, best match first.
+///
+/// IDs are compared case-insensitively, treating spaces, dashes and repeated underscores like a
+/// single underscore.
+#[must_use]
+pub fn suggestions<'a>(
+    missing: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let missing = normalize(missing);
+
+    let mut scored = candidates
+        .into_iter()
+        .filter_map(|c| score(&missing, &normalize(c)).map(|s| (s, c)))
+        .collect::<Vec<_>>();
+    scored.sort_unstable();
+    scored.dedup_by(|a, b| a.1 == b.1);
+
+    // Clearly worse candidates only add noise next to a close match.
+    let best = scored.first().map_or(0, |(s, _)| *s);
+    scored
+        .into_iter()
+        .take_while(|(s, _)| *s <= best + 1)
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, c)| c)
+        .collect()
+}
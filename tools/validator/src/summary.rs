@@ -0,0 +1,147 @@
+//! Markdown summary of a validation run, e.g. for GitHub job summaries.
+
+use crate::source::{EntityKind, SourceMap};
+use std::fmt::Write;
+use std::io::Write as _;
+use std::path::Path;
+use vacs_data_diagnostics::{Diagnostic, Severity};
+
+/// Environment variable GitHub Actions sets to the job summary file of the current step.
+pub const GITHUB_STEP_SUMMARY: &str = "GITHUB_STEP_SUMMARY";
+
+fn count(diagnostics: &[&Diagnostic], severity: Severity) -> usize {
+    diagnostics
+        .iter()
+        .filter(|d| d.severity == severity)
+        .count()
+}
+
+fn finding(out: &mut String, diagnostic: &Diagnostic) {
+    let _ = write!(out, "- **{}**", diagnostic.severity);
+    if let Some(code) = &diagnostic.code {
+        let _ = write!(out, " `{code}`");
+    }
+    if let Some(rule) = &diagnostic.rule {
+        let _ = write!(out, " `{rule}`");
+    }
+    if let Some(location) = &diagnostic.location {
+        let _ = write!(out, " `{location}`");
+    } else if !diagnostic.context.is_empty() {
+        let _ = write!(out, " `{}`", diagnostic.context.join(" > "));
+    }
+    let message = console::strip_ansi_codes(&diagnostic.message).replace('\n', " ");
+    let _ = write!(out, ": {message}");
+    if let Some(help) = &diagnostic.help {
+        let _ = write!(out, " _help: {help}_");
+    }
+    out.push('\n');
+}
+
+fn details(out: &mut String, title: &str, diagnostics: &[&Diagnostic]) {
+    if diagnostics.is_empty() {
+        return;
+    }
+    let _ = writeln!(
+        out,
+        "<details><summary>{title}: {} errors, {} warnings</summary>\n",
+        count(diagnostics, Severity::Error),
+        count(diagnostics, Severity::Warning),
+    );
+    for d in diagnostics {
+        finding(out, d);
+    }
+    out.push_str("\n</details>\n\n");
+}
+
+/// Renders the summary with one table row per FIR directory and the findings of each FIR.
+///
+/// `other_errors` counts errors not reported as diagnostics, e.g. configuration errors.
+#[must_use]
+pub fn render(input: &Path, diagnostics: &[Diagnostic], other_errors: usize) -> String {
+    let mut sources = SourceMap::new(input);
+    let firs = sources.firs();
+
+    let all = diagnostics.iter().collect::<Vec<_>>();
+    let errors = count(&all, Severity::Error) + other_errors;
+    let warnings = count(&all, Severity::Warning);
+
+    let mut out = String::from("## Dataset validation\n\n");
+    if errors > 0 {
+        let _ = writeln!(
+            out,
+            ":x: Validation failed with {errors} errors and {warnings} warnings.\n"
+        );
+    } else if warnings > 0 {
+        let _ = writeln!(
+            out,
+            ":warning: Validation successful with {warnings} warnings.\n"
+        );
+    } else {
+        out.push_str(":white_check_mark: Validation successful.\n\n");
+    }
+
+    out.push_str("| FIR | Stations | Positions | Profiles | Errors | Warnings |\n");
+    out.push_str("| :-- | --: | --: | --: | --: | --: |\n");
+    let mut sections = Vec::new();
+    for fir in &firs {
+        let findings = diagnostics
+            .iter()
+            .filter(|d| d.fir.as_deref() == Some(fir.as_str()))
+            .collect::<Vec<_>>();
+        let (e, w) = (
+            count(&findings, Severity::Error),
+            count(&findings, Severity::Warning),
+        );
+        let status = if e > 0 {
+            ":x:"
+        } else if w > 0 {
+            ":warning:"
+        } else {
+            ":white_check_mark:"
+        };
+        let _ = writeln!(
+            out,
+            "| {status} `{fir}` | {} | {} | {} | {e} | {w} |",
+            sources.fir_ids(fir, EntityKind::Station).len(),
+            sources.fir_ids(fir, EntityKind::Position).len(),
+            sources.fir_ids(fir, EntityKind::Profile).len(),
+        );
+        sections.push((fir.as_str(), findings));
+    }
+    out.push('\n');
+
+    for (fir, findings) in &sections {
+        details(&mut out, &format!("<code>{fir}</code>"), findings);
+    }
+    let other = diagnostics
+        .iter()
+        .filter(|d| {
+            d.fir
+                .as_deref()
+                .is_none_or(|fir| !firs.iter().any(|f| f == fir))
+        })
+        .collect::<Vec<_>>();
+    details(&mut out, "Other", &other);
+
+    out
+}
+
+/// Appends the summary to a file, as GitHub expects for job summaries.
+pub fn write(
+    path: &Path,
+    input: &Path,
+    diagnostics: &[Diagnostic],
+    other_errors: usize,
+) -> crate::Result<()> {
+    let summary = render(input, diagnostics, other_errors);
+
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{summary}"))
+        .map_err(|source| crate::Error::Write {
+            path: path.to_path_buf(),
+            source,
+        })
+}